@@ -1,4 +1,4 @@
-import { useState } from 'react';
+import { useRef, useState } from 'react';
 import Hero from './components/Hero';
 import InputForm from './components/InputForm';
 import ResultCard from './components/ResultCard';
@@ -11,6 +11,7 @@ function App() {
   const [isLoading, setIsLoading] = useState(false);
   const [error, setError] = useState(null);
   const [progress, setProgress] = useState('');
+  const socketRef = useRef<WebSocket | null>(null);
 
   const handleValidate = async (id) => {
     setIsLoading(true);
@@ -23,14 +24,29 @@ function App() {
     const wsUrl = `${protocol}//${window.location.hostname}:8000/ws/validate/${id}`;
 
     const ws = new WebSocket(wsUrl);
+    socketRef.current = ws;
+
+    ws.onopen = () => {
+      ws.send(JSON.stringify({ type: 'start' }));
+    };
 
     ws.onmessage = (event) => {
       try {
         const data = JSON.parse(event.data);
-        if (data.type === 'progress') {
-          setProgress(data.status);
-        } else if (data.is_valid !== undefined) {
-          setReport(data);
+        if (data.type === 'started') {
+          setProgress('Fetching deck...');
+        } else if (data.type === 'deck_fetched') {
+          setProgress(`Checking ${data.name} by ${data.author} (${data.cards} cards)...`);
+        } else if (data.type === 'card_checked') {
+          setProgress(`${data.validator}: ${data.checked}/${data.total} - ${data.card}`);
+        } else if (data.type === 'validator_done') {
+          setProgress(`Completed ${data.validator} (${data.completed}/${data.total})`);
+        } else if (data.type === 'completed') {
+          setReport(data.report);
+          ws.close();
+          setIsLoading(false);
+        } else if (data.type === 'cancelled') {
+          setProgress('');
           ws.close();
           setIsLoading(false);
         } else if (data.type === 'error') {
@@ -50,6 +66,13 @@ function App() {
     };
   };
 
+  const handleCancel = () => {
+    const ws = socketRef.current;
+    if (ws && ws.readyState === WebSocket.OPEN) {
+      ws.send(JSON.stringify({ type: 'cancel' }));
+    }
+  };
+
   return (
     <div className="App">
       <div style={{ display: 'flex', justifyContent: 'center', gap: '1rem', marginBottom: '2rem' }}>
@@ -78,7 +101,7 @@ function App() {
       {activeTab === 'home' ? (
         <>
           <Hero />
-          <InputForm onSubmit={handleValidate} isLoading={isLoading} progress={progress} />
+          <InputForm onSubmit={handleValidate} onCancel={handleCancel} isLoading={isLoading} progress={progress} />
 
           {error && (
             <div style={{ color: 'var(--error-color)', marginBottom: '2rem', display: 'flex', alignItems: 'center', justifyContent: 'center', gap: '0.5rem' }}>
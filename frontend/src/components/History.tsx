@@ -1,26 +1,69 @@
 import { useState, useEffect } from 'react';
 import { motion } from 'framer-motion';
-import { Clock, CheckCircle, XCircle, ChevronRight } from 'lucide-react';
+import { Clock, CheckCircle, XCircle, AlertTriangle, ChevronRight, Search } from 'lucide-react';
 import ResultCard from './ResultCard';
 
+const EMPTY_FILTERS = {
+  name: '',
+  author: '',
+  card: '',
+  valid: '',
+  ruleset: '',
+  from: '',
+  to: '',
+  sort: 'newest',
+};
+
 export default function History() {
   const [history, setHistory] = useState([]);
   const [selectedReport, setSelectedReport] = useState(null);
   const [isLoading, setIsLoading] = useState(true);
+  const [filters, setFilters] = useState(EMPTY_FILTERS);
+  const [error, setError] = useState(null);
 
-  useEffect(() => {
-    fetch('/history')
+  const fetchHistory = (activeFilters) => {
+    const params = new URLSearchParams();
+    Object.entries(activeFilters).forEach(([key, value]) => {
+      if (value.trim()) {
+        params.set(key, value.trim());
+      }
+    });
+
+    setIsLoading(true);
+    setError(null);
+    fetch(`/history?${params}`)
       .then(res => res.json())
       .then(data => {
-        setHistory(data);
+        if (Array.isArray(data)) {
+          setHistory(data);
+        } else {
+          setHistory([]);
+          setError(data.message || 'Failed to load history');
+        }
         setIsLoading(false);
       })
       .catch(err => {
         console.error("Failed to fetch history:", err);
         setIsLoading(false);
       });
+  };
+
+  useEffect(() => {
+    fetchHistory(EMPTY_FILTERS);
   }, []);
 
+  const updateFilter = (key) => (e) => setFilters({ ...filters, [key]: e.target.value });
+
+  const handleSearch = (e) => {
+    e.preventDefault();
+    fetchHistory(filters);
+  };
+
+  const handleReset = () => {
+    setFilters(EMPTY_FILTERS);
+    fetchHistory(EMPTY_FILTERS);
+  };
+
   if (selectedReport) {
     return (
       <div>
@@ -46,6 +89,47 @@ export default function History() {
         <Clock /> Recent Analyses
       </h2>
 
+      <form
+        onSubmit={handleSearch}
+        style={{ display: 'grid', gridTemplateColumns: 'repeat(auto-fit, minmax(180px, 1fr))', gap: '0.75rem', marginBottom: '1.5rem' }}
+      >
+        <input type="text" value={filters.name} onChange={updateFilter('name')} placeholder="Deck name contains" />
+        <input type="text" value={filters.author} onChange={updateFilter('author')} placeholder="Author" />
+        <input type="text" value={filters.card} onChange={updateFilter('card')} placeholder="Contains card" />
+        <select value={filters.valid} onChange={updateFilter('valid')}>
+          <option value="">Any verdict</option>
+          <option value="true">Valid</option>
+          <option value="false">Invalid</option>
+          <option value="inconclusive">Inconclusive</option>
+        </select>
+        <select value={filters.ruleset} onChange={updateFilter('ruleset')}>
+          <option value="">Any ruleset</option>
+          <option value="house">House</option>
+          <option value="bracket2">Bracket 2</option>
+          <option value="bracket3">Bracket 3</option>
+        </select>
+        <select value={filters.sort} onChange={updateFilter('sort')}>
+          <option value="newest">Newest first</option>
+          <option value="oldest">Oldest first</option>
+          <option value="name">Deck name</option>
+          <option value="author">Author</option>
+        </select>
+        <input type="date" value={filters.from} onChange={updateFilter('from')} title="Validated from" />
+        <input type="date" value={filters.to} onChange={updateFilter('to')} title="Validated until" />
+        <div style={{ display: 'flex', gap: '0.5rem' }}>
+          <button type="submit" style={{ display: 'flex', alignItems: 'center', gap: '0.4rem' }}>
+            <Search size={16} /> Search
+          </button>
+          <button type="button" onClick={handleReset} style={{ background: 'transparent', color: 'var(--secondary-color)' }}>
+            Reset
+          </button>
+        </div>
+      </form>
+
+      {error && (
+        <p style={{ color: 'var(--error-color)' }}>{error}</p>
+      )}
+
       {isLoading ? (
         <p>Loading history...</p>
       ) : history.length === 0 ? (
@@ -72,12 +156,18 @@ export default function History() {
             >
               {report.is_valid ? (
                 <CheckCircle color="var(--success-color)" />
+              ) : report.inconclusive ? (
+                <AlertTriangle color="var(--secondary-color)" />
               ) : (
                 <XCircle color="var(--error-color)" />
               )}
               <div style={{ flex: 1 }}>
                 <h3 style={{ margin: 0, fontSize: '1.1rem' }}>{report.name}</h3>
-                <p style={{ margin: 0, fontSize: '0.9rem', color: 'var(--secondary-color)' }}>by {report.author}</p>
+                <p style={{ margin: 0, fontSize: '0.9rem', color: 'var(--secondary-color)' }}>
+                  by {report.author}
+                  {report.ruleset && ` · ${report.ruleset}`}
+                  {report.created_at && ` · ${new Date(report.created_at).toLocaleDateString()}`}
+                </p>
               </div>
               <ChevronRight color="var(--secondary-color)" />
             </motion.div>
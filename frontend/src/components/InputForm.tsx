@@ -4,11 +4,12 @@ import { Search, Loader2 } from 'lucide-react';
 
 interface InputFormProps {
     onSubmit: (id: string) => void;
+    onCancel: () => void;
     isLoading: boolean;
     progress: string;
 }
 
-export default function InputForm({ onSubmit, isLoading, progress }: InputFormProps) {
+export default function InputForm({ onSubmit, onCancel, isLoading, progress }: InputFormProps) {
     const [input, setInput] = useState('');
 
     const progressText = isLoading ? (progress || 'Initializing...') : '';
@@ -67,6 +68,11 @@ export default function InputForm({ onSubmit, isLoading, progress }: InputFormPr
                     {progressText}
                 </motion.p>
             )}
+            {isLoading && (
+                <button type="button" onClick={onCancel} style={{ marginTop: '0.5rem', background: 'transparent', color: 'var(--secondary-color)' }}>
+                    Cancel
+                </button>
+            )}
             <style>{`
         .spin {
           animation: spin 1s linear infinite;
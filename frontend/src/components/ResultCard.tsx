@@ -3,6 +3,8 @@ import { CheckCircle, XCircle, AlertTriangle } from 'lucide-react';
 
 export default function ResultCard({ report }) {
     const isValid = report.is_valid;
+    const isInconclusive = report.inconclusive;
+    const verdictColor = isValid ? 'var(--success-color)' : isInconclusive ? 'var(--secondary-color)' : 'var(--error-color)';
 
     const container = {
         hidden: { opacity: 0 },
@@ -30,6 +32,8 @@ export default function ResultCard({ report }) {
             <motion.div variants={item} style={{ display: 'flex', alignItems: 'center', gap: '1rem', marginBottom: '2rem', borderBottom: '1px solid var(--border-color)', paddingBottom: '1rem' }}>
                 {isValid ? (
                     <CheckCircle size={48} color="var(--success-color)" />
+                ) : isInconclusive ? (
+                    <AlertTriangle size={48} color={verdictColor} />
                 ) : (
                     <XCircle size={48} color="var(--error-color)" />
                 )}
@@ -37,12 +41,14 @@ export default function ResultCard({ report }) {
                     <h2 style={{ margin: 0 }}>{report.name}</h2>
                     <p style={{ margin: 0, color: 'var(--secondary-color)' }}>by {report.author}</p>
                 </div>
-                <div style={{ marginLeft: 'auto', padding: '0.5rem 1rem', borderRadius: '2rem', background: isValid ? 'rgba(34, 197, 94, 0.1)' : 'rgba(239, 68, 68, 0.1)', color: isValid ? 'var(--success-color)' : 'var(--error-color)', fontWeight: 'bold' }}>
-                    {isValid ? 'VALID' : 'INVALID'}
+                <div style={{ marginLeft: 'auto', padding: '0.5rem 1rem', borderRadius: '2rem', background: isValid ? 'rgba(34, 197, 94, 0.1)' : isInconclusive ? 'rgba(148, 163, 184, 0.1)' : 'rgba(239, 68, 68, 0.1)', color: verdictColor, fontWeight: 'bold' }}>
+                    {isValid ? 'VALID' : isInconclusive ? 'INCONCLUSIVE' : 'INVALID'}
                 </div>
             </motion.div>
 
             <div style={{ display: 'grid', gap: '1rem' }}>
+                <Section title="Incomplete Lookups" items={report.incomplete_lookups} icon={<AlertTriangle size={16} />} />
+                <Section title="Unknown Cards" items={report.unknown_cards?.map(c => [c, 'Not found on Scryfall'])} icon={<AlertTriangle size={16} />} />
                 <Section title="Non-Land Tutors" items={report.non_land_tutors} icon={<AlertTriangle size={16} />} />
                 <Section title="Mass Land Denial" items={report.mass_land_denial_cards} icon={<AlertTriangle size={16} />} />
                 <Section title="Commander Tutors" items={report.commander_tutors.map(c => [c, 'Commander Tutor'])} icon={<AlertTriangle size={16} />} />
@@ -0,0 +1,106 @@
+use crate::cache::{CacheEntrySummary, CacheSource, CacheStats};
+use crate::config::AppConfig;
+use crate::errors::AppError;
+use crate::upstream::Upstream;
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome};
+use rocket::{Request, State, serde::json::Json};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+const DEFAULT_ENTRY_LIMIT: u64 = 100;
+const MAX_ENTRY_LIMIT: u64 = 1000;
+
+pub struct AdminToken;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AdminToken {
+    type Error = AppError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let expected = match request
+            .rocket()
+            .state::<AppConfig>()
+            .and_then(|config| config.admin_token.as_deref())
+        {
+            Some(token) if !token.is_empty() => token,
+            _ => return Outcome::Error((Status::Unauthorized, AppError::Unauthorized)),
+        };
+
+        let provided = request
+            .headers()
+            .get_one("Authorization")
+            .and_then(|value| value.strip_prefix("Bearer "));
+
+        if provided.is_some_and(|provided| tokens_match(provided, expected)) {
+            Outcome::Success(AdminToken)
+        } else {
+            Outcome::Error((Status::Unauthorized, AppError::Unauthorized))
+        }
+    }
+}
+
+// Compares fixed-length digests without short-circuiting so timing reveals nothing about the token.
+fn tokens_match(provided: &str, expected: &str) -> bool {
+    let provided = Sha256::digest(provided.as_bytes());
+    let expected = Sha256::digest(expected.as_bytes());
+    provided
+        .iter()
+        .zip(expected.iter())
+        .fold(0, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+#[derive(Serialize, Debug)]
+pub struct PurgeResult {
+    pub removed: u64,
+}
+
+fn parse_source(source: &str) -> Result<CacheSource, AppError> {
+    CacheSource::from_name(source)
+        .ok_or_else(|| AppError::InvalidRequest(format!("Unknown cache source {}", source)))
+}
+
+#[get("/admin/cache")]
+pub async fn cache_stats(
+    admin: Result<AdminToken, AppError>,
+    upstream: &State<Upstream>,
+) -> Result<Json<Vec<CacheStats>>, AppError> {
+    admin?;
+    Ok(Json(upstream.cache.stats().await?))
+}
+
+#[get("/admin/cache/<source>?<limit>")]
+pub async fn cache_entries(
+    source: &str,
+    limit: Option<u64>,
+    admin: Result<AdminToken, AppError>,
+    upstream: &State<Upstream>,
+) -> Result<Json<Vec<CacheEntrySummary>>, AppError> {
+    admin?;
+    let source = parse_source(source)?;
+    let limit = limit.unwrap_or(DEFAULT_ENTRY_LIMIT).min(MAX_ENTRY_LIMIT);
+    Ok(Json(upstream.cache.entries(source, limit).await?))
+}
+
+#[delete("/admin/cache")]
+pub async fn purge_cache(
+    admin: Result<AdminToken, AppError>,
+    upstream: &State<Upstream>,
+) -> Result<Json<PurgeResult>, AppError> {
+    admin?;
+    let removed = upstream.cache.purge(None).await?;
+    Ok(Json(PurgeResult { removed }))
+}
+
+#[delete("/admin/cache/<source>")]
+pub async fn purge_cache_source(
+    source: &str,
+    admin: Result<AdminToken, AppError>,
+    upstream: &State<Upstream>,
+) -> Result<Json<PurgeResult>, AppError> {
+    admin?;
+    let source = parse_source(source)?;
+    let removed = upstream.cache.purge(Some(source)).await?;
+    Ok(Json(PurgeResult { removed }))
+}
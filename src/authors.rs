@@ -0,0 +1,85 @@
+use crate::config::AppConfig;
+use crate::errors::AppError;
+use crate::jobs::{JobRunner, JobStatus};
+use crate::moxfield::fetch_user_decks;
+use crate::persistence::HistoryStore;
+use crate::ruleset::Ruleset;
+use crate::upstream::Upstream;
+use chrono::{DateTime, Utc};
+use rocket::response::status::Accepted;
+use rocket::{State, serde::json::Json};
+use serde::Serialize;
+
+#[derive(Serialize, Debug)]
+pub struct AuthorDeck {
+    pub deck_id: String,
+    pub name: String,
+    pub checks: u64,
+    pub is_valid: bool,
+    pub inconclusive: bool,
+    pub ruleset: Option<Ruleset>,
+    pub validated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AuthorProfile {
+    pub author: String,
+    pub reports: u64,
+    pub passed: u64,
+    pub decks: Vec<AuthorDeck>,
+}
+
+#[get("/authors/<user_name>")]
+pub async fn get_author(
+    user_name: &str,
+    store: &State<HistoryStore>,
+) -> Result<Json<AuthorProfile>, AppError> {
+    let rows = store.author_decks(user_name).await?;
+    let Some(latest) = rows.first() else {
+        return Err(AppError::AuthorNotFound(user_name.to_string()));
+    };
+
+    let profile = AuthorProfile {
+        author: latest.author.clone(),
+        reports: rows.iter().map(|row| row.checks as u64).sum(),
+        passed: rows.iter().map(|row| row.passed as u64).sum(),
+        // Reports saved before deck ids were stored count towards the totals only.
+        decks: rows
+            .into_iter()
+            .filter_map(|row| {
+                Some(AuthorDeck {
+                    deck_id: row.deck_id?,
+                    name: row.name,
+                    checks: row.checks as u64,
+                    is_valid: row.is_valid,
+                    inconclusive: row.inconclusive,
+                    ruleset: row.ruleset.as_deref().and_then(Ruleset::from_name),
+                    validated_at: row.created_at,
+                })
+            })
+            .collect(),
+    };
+
+    Ok(Json(profile))
+}
+
+#[post("/authors/<user_name>/validate")]
+pub async fn validate_author(
+    user_name: &str,
+    upstream: &State<Upstream>,
+    runner: &State<JobRunner>,
+    config: &State<AppConfig>,
+) -> Result<Accepted<Json<JobStatus>>, AppError> {
+    let ids = fetch_user_decks(upstream, user_name)
+        .await?
+        .into_iter()
+        .filter(|deck| {
+            deck.format.eq_ignore_ascii_case("commander")
+                && !deck.visibility.eq_ignore_ascii_case("private")
+        })
+        .map(|deck| deck.public_id)
+        .collect();
+
+    let job = runner.enqueue(config.default_ruleset, ids).await?;
+    Ok(Accepted(Json(runner.status(job.id).await?)))
+}
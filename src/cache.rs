@@ -0,0 +1,361 @@
+use crate::errors::AppError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use moka::future::Cache;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheSource {
+    Scryfall,
+    Spellbook,
+}
+
+impl CacheSource {
+    pub const ALL: [CacheSource; 2] = [CacheSource::Scryfall, CacheSource::Spellbook];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CacheSource::Scryfall => "scryfall",
+            CacheSource::Spellbook => "spellbook",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|source| source.name().eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    pub scryfall_ttl: Duration,
+    pub spellbook_ttl: Duration,
+    pub max_memory_entries: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            scryfall_ttl: Duration::from_secs(7 * 24 * 3600),
+            spellbook_ttl: Duration::from_secs(24 * 3600),
+            max_memory_entries: 50_000,
+        }
+    }
+}
+
+impl CacheConfig {
+    pub fn ttl(&self, source: CacheSource) -> Duration {
+        match source {
+            CacheSource::Scryfall => self.scryfall_ttl,
+            CacheSource::Spellbook => self.spellbook_ttl,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct CacheStats {
+    pub source: CacheSource,
+    pub ttl_secs: u64,
+    pub memory_entries: u64,
+    pub disk_entries: u64,
+    pub memory_hits: u64,
+    pub disk_hits: u64,
+    pub misses: u64,
+    pub disk_errors: u64,
+    pub hit_rate: f64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CacheEntrySummary {
+    pub key: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CachedValue {
+    pub value: serde_json::Value,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait CacheBackend: Send + Sync {
+    async fn get(&self, source: CacheSource, key: &str) -> Result<Option<CachedValue>, AppError>;
+
+    async fn insert(
+        &self,
+        source: CacheSource,
+        key: String,
+        entry: CachedValue,
+    ) -> Result<(), AppError>;
+
+    async fn count(&self, source: CacheSource) -> Result<u64, AppError>;
+
+    async fn entries(
+        &self,
+        source: CacheSource,
+        limit: u64,
+    ) -> Result<Vec<CacheEntrySummary>, AppError>;
+
+    async fn purge(&self, source: CacheSource) -> Result<u64, AppError>;
+
+    async fn purge_expired(&self) -> Result<u64, AppError>;
+}
+
+struct SourceTier {
+    memory: Cache<String, CachedValue>,
+    ttl: Duration,
+    memory_hits: AtomicU64,
+    disk_hits: AtomicU64,
+    misses: AtomicU64,
+    disk_errors: AtomicU64,
+}
+
+struct Tiers {
+    scryfall: SourceTier,
+    spellbook: SourceTier,
+}
+
+#[derive(Clone)]
+pub struct LookupCache {
+    backend: Option<Arc<dyn CacheBackend>>,
+    tiers: Arc<Tiers>,
+}
+
+impl LookupCache {
+    pub fn new(config: CacheConfig) -> Self {
+        let tier = |source: CacheSource| SourceTier {
+            memory: Cache::builder()
+                .max_capacity(config.max_memory_entries)
+                .time_to_live(config.ttl(source))
+                .build(),
+            ttl: config.ttl(source),
+            memory_hits: AtomicU64::new(0),
+            disk_hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            disk_errors: AtomicU64::new(0),
+        };
+
+        Self {
+            backend: None,
+            tiers: Arc::new(Tiers {
+                scryfall: tier(CacheSource::Scryfall),
+                spellbook: tier(CacheSource::Spellbook),
+            }),
+        }
+    }
+
+    pub fn with_backend(mut self, backend: impl CacheBackend + 'static) -> Self {
+        self.backend = Some(Arc::new(backend));
+        self
+    }
+
+    fn tier(&self, source: CacheSource) -> &SourceTier {
+        match source {
+            CacheSource::Scryfall => &self.tiers.scryfall,
+            CacheSource::Spellbook => &self.tiers.spellbook,
+        }
+    }
+
+    pub async fn get<T: DeserializeOwned>(&self, source: CacheSource, key: &str) -> Option<T> {
+        let tier = self.tier(source);
+
+        if let Some(entry) = tier.memory.get(key).await
+            && entry.expires_at > Utc::now()
+            && let Ok(result) = serde_json::from_value(entry.value)
+        {
+            tier.memory_hits.fetch_add(1, Ordering::Relaxed);
+            return Some(result);
+        }
+
+        if let Some(backend) = &self.backend {
+            match backend.get(source, key).await {
+                Ok(Some(entry)) => {
+                    if let Ok(result) = serde_json::from_value(entry.value.clone()) {
+                        // Promoted entries keep the expiry they were written with.
+                        tier.memory.insert(key.to_string(), entry).await;
+                        tier.disk_hits.fetch_add(1, Ordering::Relaxed);
+                        return Some(result);
+                    }
+                }
+                Ok(None) => {}
+                Err(_) => {
+                    tier.disk_errors.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+
+        tier.misses.fetch_add(1, Ordering::Relaxed);
+        None
+    }
+
+    pub async fn insert<T: Serialize>(&self, source: CacheSource, key: String, value: &T) {
+        let Ok(value) = serde_json::to_value(value) else {
+            return;
+        };
+        let tier = self.tier(source);
+        let entry = CachedValue {
+            value,
+            expires_at: Utc::now() + chrono::Duration::from_std(tier.ttl).unwrap_or_default(),
+        };
+        tier.memory.insert(key.clone(), entry.clone()).await;
+
+        if let Some(backend) = &self.backend
+            && backend.insert(source, key, entry).await.is_err()
+        {
+            tier.disk_errors.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub async fn stats(&self) -> Result<Vec<CacheStats>, AppError> {
+        let mut stats = Vec::new();
+        for source in CacheSource::ALL {
+            let tier = self.tier(source);
+            tier.memory.run_pending_tasks().await;
+
+            let disk_entries = match &self.backend {
+                Some(backend) => backend.count(source).await?,
+                None => 0,
+            };
+
+            let memory_hits = tier.memory_hits.load(Ordering::Relaxed);
+            let disk_hits = tier.disk_hits.load(Ordering::Relaxed);
+            let misses = tier.misses.load(Ordering::Relaxed);
+            let disk_errors = tier.disk_errors.load(Ordering::Relaxed);
+            let lookups = memory_hits + disk_hits + misses;
+
+            stats.push(CacheStats {
+                source,
+                ttl_secs: tier.ttl.as_secs(),
+                memory_entries: tier.memory.entry_count(),
+                disk_entries,
+                memory_hits,
+                disk_hits,
+                misses,
+                disk_errors,
+                hit_rate: if lookups == 0 {
+                    0.0
+                } else {
+                    (memory_hits + disk_hits) as f64 / lookups as f64
+                },
+            });
+        }
+        Ok(stats)
+    }
+
+    pub async fn entries(
+        &self,
+        source: CacheSource,
+        limit: u64,
+    ) -> Result<Vec<CacheEntrySummary>, AppError> {
+        match &self.backend {
+            Some(backend) => backend.entries(source, limit).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub async fn purge(&self, source: Option<CacheSource>) -> Result<u64, AppError> {
+        let sources = match source {
+            Some(source) => vec![source],
+            None => CacheSource::ALL.to_vec(),
+        };
+
+        let mut removed = 0;
+        for source in sources {
+            self.tier(source).memory.invalidate_all();
+            if let Some(backend) = &self.backend {
+                removed += backend.purge(source).await?;
+            }
+        }
+        Ok(removed)
+    }
+
+    pub async fn purge_expired(&self) -> Result<u64, AppError> {
+        match &self.backend {
+            Some(backend) => backend.purge_expired().await,
+            None => Ok(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemoryBackend {
+        entries: Mutex<std::collections::HashMap<String, CachedValue>>,
+    }
+
+    #[async_trait]
+    impl CacheBackend for MemoryBackend {
+        async fn get(
+            &self,
+            _source: CacheSource,
+            key: &str,
+        ) -> Result<Option<CachedValue>, AppError> {
+            let entries = self.entries.lock().unwrap();
+            Ok(entries
+                .get(key)
+                .filter(|entry| entry.expires_at > Utc::now())
+                .cloned())
+        }
+
+        async fn insert(
+            &self,
+            _source: CacheSource,
+            key: String,
+            entry: CachedValue,
+        ) -> Result<(), AppError> {
+            self.entries.lock().unwrap().insert(key, entry);
+            Ok(())
+        }
+
+        async fn count(&self, _source: CacheSource) -> Result<u64, AppError> {
+            Ok(self.entries.lock().unwrap().len() as u64)
+        }
+
+        async fn entries(
+            &self,
+            _source: CacheSource,
+            _limit: u64,
+        ) -> Result<Vec<CacheEntrySummary>, AppError> {
+            Ok(Vec::new())
+        }
+
+        async fn purge(&self, _source: CacheSource) -> Result<u64, AppError> {
+            Ok(0)
+        }
+
+        async fn purge_expired(&self) -> Result<u64, AppError> {
+            Ok(0)
+        }
+    }
+
+    #[tokio::test]
+    async fn promoted_entries_keep_their_expiry() {
+        let backend = MemoryBackend::default();
+        backend.entries.lock().unwrap().insert(
+            "Sol Ring".to_string(),
+            CachedValue {
+                value: serde_json::json!("artifact"),
+                expires_at: Utc::now() + chrono::Duration::milliseconds(100),
+            },
+        );
+        let cache = LookupCache::new(CacheConfig::default()).with_backend(backend);
+
+        let promoted: Option<String> = cache.get(CacheSource::Scryfall, "Sol Ring").await;
+        assert_eq!(promoted.as_deref(), Some("artifact"));
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let expired: Option<String> = cache.get(CacheSource::Scryfall, "Sol Ring").await;
+        assert_eq!(expired, None);
+    }
+}
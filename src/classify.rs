@@ -0,0 +1,335 @@
+use crate::models::ScryfallCard;
+
+const LAND_SEARCH_TERMS: [&str; 6] = ["land", "plains", "island", "swamp", "mountain", "forest"];
+const MASS_REMOVAL_VERBS: [&str; 6] = [
+    "destroy all",
+    "exile all",
+    "return all",
+    "sacrifice all",
+    "sacrifices all",
+    "each player sacrifices",
+];
+const UNTAP_LOCK_PHRASES: [&str; 2] = ["don't untap", "can't untap more than"];
+
+fn clauses(card: &ScryfallCard) -> impl Iterator<Item = String> + '_ {
+    card.oracle_texts()
+        .into_iter()
+        .flat_map(|oracle_text| oracle_text.split(['.', '\n']))
+        .map(|clause| clause.trim().to_lowercase())
+        .filter(|clause| !clause.is_empty())
+}
+
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+}
+
+fn is_land_word(word: &str) -> bool {
+    LAND_SEARCH_TERMS
+        .iter()
+        .any(|term| word == *term || word.strip_suffix('s') == Some(term))
+}
+
+fn mentions_land(text: &str) -> bool {
+    words(text).any(is_land_word)
+}
+
+// "permanents" covers lands unless it is qualified as "nonland permanents".
+fn targets_lands(target: &str) -> bool {
+    let words: Vec<&str> = words(target).collect();
+    words.iter().copied().any(is_land_word)
+        || words.iter().enumerate().any(|(index, word)| {
+            matches!(*word, "permanent" | "permanents")
+                && (index == 0 || words[index - 1] != "nonland")
+        })
+}
+
+// The card types a search may find, e.g. "a creature or land" yields "a creature" and "land".
+fn search_alternatives(target: &str) -> impl Iterator<Item = &str> {
+    let target = target.split(" card").next().unwrap_or(target);
+    target
+        .split(" and/or ")
+        .flat_map(|part| part.split(" or "))
+        .flat_map(|part| part.split(','))
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+}
+
+// Targets of "search(es) ... library ... for", skipping searches of another player's library.
+fn search_targets(clause: &str) -> impl Iterator<Item = &str> {
+    clause
+        .match_indices("search")
+        .filter_map(move |(index, _)| {
+            let rest = &clause[index..];
+            if !(rest.starts_with("search ") || rest.starts_with("searches ")) {
+                return None;
+            }
+            let (source, target) = rest.split_once(" for ")?;
+            (source.contains("library") && !source.contains("target")).then_some(target)
+        })
+}
+
+impl ScryfallCard {
+    pub fn is_tutor(&self) -> bool {
+        clauses(self).any(|clause| {
+            search_targets(&clause).any(|target| !search_alternatives(target).all(mentions_land))
+        })
+    }
+
+    pub fn is_mass_land_denial(&self) -> bool {
+        clauses(self).any(|clause| {
+            let removes_lands = !clause.contains("graveyard")
+                && MASS_REMOVAL_VERBS.iter().any(|verb| {
+                    clause.find(verb).is_some_and(|index| {
+                        let target = &clause[index + verb.len()..];
+                        targets_lands(target) && (*verb != "return all" || target.contains("hand"))
+                    })
+                });
+            let locks_lands = UNTAP_LOCK_PHRASES
+                .iter()
+                .any(|phrase| clause.contains(phrase) && mentions_land(&clause));
+            removes_lands || locks_lands
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{ScryfallCard, ScryfallCardFace};
+    use std::collections::HashMap;
+
+    fn card(name: &str, oracle_text: &str) -> ScryfallCard {
+        ScryfallCard {
+            name: name.to_string(),
+            oracle_text: Some(oracle_text.to_string()),
+            type_line: String::new(),
+            card_faces: None,
+            color_identity: Vec::new(),
+            legalities: HashMap::new(),
+            game_changer: false,
+        }
+    }
+
+    fn split_card(name: &str, faces: &[(&str, &str)]) -> ScryfallCard {
+        ScryfallCard {
+            oracle_text: None,
+            card_faces: Some(
+                faces
+                    .iter()
+                    .map(|(name, oracle_text)| ScryfallCardFace {
+                        name: name.to_string(),
+                        oracle_text: Some(oracle_text.to_string()),
+                        type_line: None,
+                    })
+                    .collect(),
+            ),
+            ..card(name, "")
+        }
+    }
+
+    #[test]
+    fn flags_mass_land_denial() {
+        let cards = [
+            card("Armageddon", "Destroy all lands."),
+            card("Ravages of War", "Destroy all lands."),
+            card("Boil", "Destroy all Islands."),
+            card("Flashfires", "Destroy all Plains."),
+            card("Tsunami", "Destroy all Islands."),
+            card(
+                "Tectonic Break",
+                "Each player sacrifices X lands of their choice.",
+            ),
+            card(
+                "Choke",
+                "Islands don't untap during their controllers' untap steps.",
+            ),
+            card("Upheaval", "Return all permanents to their owners' hands."),
+            card("Apocalypse", "Exile all permanents. You discard your hand."),
+            card(
+                "Winter Orb",
+                "As long as Winter Orb is untapped, players can't untap more than one land during their untap steps.",
+            ),
+        ];
+
+        for card in cards {
+            assert!(card.is_mass_land_denial(), "{}", card.name);
+            assert!(!card.is_tutor(), "{}", card.name);
+        }
+    }
+
+    #[test]
+    fn ignores_spot_and_non_land_removal() {
+        let cards = [
+            card(
+                "Wrath of God",
+                "Destroy all creatures. They can't be regenerated.",
+            ),
+            card(
+                "Evolving Wilds",
+                "{T}, Sacrifice Evolving Wilds: Search your library for a basic land card, put it onto the battlefield tapped, then shuffle.",
+            ),
+            card(
+                "Cyclonic Rift",
+                "Return target nonland permanent you don't control to its owner's hand.\nOverload {6}{U}",
+            ),
+            card(
+                "Sleep",
+                "Tap all creatures target player controls. Those creatures don't untap during that player's next untap step.",
+            ),
+            card(
+                "Ruinous Ultimatum",
+                "Destroy all nonland permanents your opponents control.",
+            ),
+            card("Evacuation", "Return all creatures to their owners' hands."),
+        ];
+
+        for card in cards {
+            assert!(!card.is_mass_land_denial(), "{}", card.name);
+        }
+    }
+
+    #[test]
+    fn ignores_land_recursion() {
+        let cards = [
+            card(
+                "Splendid Reclamation",
+                "Return all land cards from your graveyard to the battlefield tapped.",
+            ),
+            card(
+                "Planar Birth",
+                "Return all basic land cards from all graveyards to the battlefield tapped under their owners' control.",
+            ),
+            card(
+                "Crucible of Worlds",
+                "You may play lands from your graveyard.",
+            ),
+            card(
+                "Scapeshift",
+                "Sacrifice any number of lands. Search your library for up to that many land cards, put them onto the battlefield tapped, then shuffle.",
+            ),
+        ];
+
+        for card in cards {
+            assert!(!card.is_mass_land_denial(), "{}", card.name);
+            assert!(!card.is_tutor(), "{}", card.name);
+        }
+    }
+
+    #[test]
+    fn flags_non_land_tutors() {
+        let cards = [
+            card(
+                "Demonic Tutor",
+                "Search your library for a card, put that card into your hand, then shuffle.",
+            ),
+            card(
+                "Gamble",
+                "Search your library for a card, put that card into your hand, discard a card at random, then shuffle.",
+            ),
+            card(
+                "Nonland Tutor",
+                "Search your library for a nonland card, reveal it, put it into your hand, then shuffle.",
+            ),
+            split_card(
+                "Research // Development",
+                &[
+                    (
+                        "Research",
+                        "Choose up to four cards you own from outside the game and shuffle them into your library.",
+                    ),
+                    (
+                        "Development",
+                        "Create a 3/1 red Elemental creature token unless any opponent has you draw a card. Repeat this process two more times.",
+                    ),
+                ],
+            ),
+        ];
+
+        for card in &cards[..3] {
+            assert!(card.is_tutor(), "{}", card.name);
+            assert!(!card.is_mass_land_denial(), "{}", card.name);
+        }
+        assert!(!cards[3].is_tutor());
+    }
+
+    #[test]
+    fn matches_search_wording_variants() {
+        let cards = [
+            card(
+                "Finale of Devastation",
+                "Search your library and/or graveyard for a creature card with mana value X or less and put it onto the battlefield. If you search your library this way, shuffle.",
+            ),
+            card(
+                "Scheming Symmetry",
+                "Choose two target players. Each of them searches their library for a card, then shuffles and puts that card on top.",
+            ),
+            card(
+                "Traverse the Ulvenwald",
+                "Search your library for a basic land card, reveal it, put it into your hand, then shuffle.\nDelirium — If there are four or more card types among cards in your graveyard, instead search your library for a creature or land card, reveal it, put it into your hand, then shuffle.",
+            ),
+        ];
+
+        for card in cards {
+            assert!(card.is_tutor(), "{}", card.name);
+        }
+        assert!(
+            !card(
+                "Jester's Cap",
+                "{2}, {T}, Sacrifice Jester's Cap: Search target player's library for three cards and exile them. That player then shuffles.",
+            )
+            .is_tutor()
+        );
+    }
+
+    #[test]
+    fn ignores_land_tutors() {
+        let cards = [
+            card(
+                "Cultivate",
+                "Search your library for up to two basic land cards, reveal those cards, put one onto the battlefield tapped and the other into your hand, then shuffle.",
+            ),
+            card(
+                "Evolving Wilds",
+                "{T}, Sacrifice Evolving Wilds: Search your library for a basic land card, put it onto the battlefield tapped, then shuffle.",
+            ),
+            card(
+                "Flooded Strand",
+                "{T}, Pay 1 life, Sacrifice Flooded Strand: Search your library for a Plains or Island card, put it onto the battlefield, then shuffle.",
+            ),
+            split_card(
+                "Spring // Mind",
+                &[
+                    (
+                        "Spring",
+                        "Search your library for a basic land card, put it onto the battlefield tapped, then shuffle.",
+                    ),
+                    (
+                        "Mind",
+                        "Aftermath (Cast this spell only from your graveyard. Then exile it.)\nDraw two cards.",
+                    ),
+                ],
+            ),
+        ];
+
+        for card in cards {
+            assert!(!card.is_tutor(), "{}", card.name);
+        }
+    }
+
+    #[test]
+    fn scans_every_face_of_split_cards() {
+        let card = split_card(
+            "Tutor // Quake",
+            &[
+                (
+                    "Tutor",
+                    "Search your library for a card, put it into your hand, then shuffle.",
+                ),
+                ("Quake", "Destroy all lands."),
+            ],
+        );
+
+        assert!(card.is_tutor());
+        assert!(card.is_mass_land_denial());
+    }
+}
@@ -0,0 +1,333 @@
+use crate::cache::LookupCache;
+use crate::config::{AppConfig, is_database_url};
+use crate::decklist::parse_decklist;
+use crate::errors::AppError;
+use crate::http::HttpClient;
+use crate::models::Report;
+use crate::moxfield::{fetch_list, parse_deck_id};
+use crate::ruleset::Ruleset;
+use crate::upstream::Upstream;
+use std::path::Path;
+
+pub const EXIT_VALID: i32 = 0;
+pub const EXIT_INVALID: i32 = 1;
+pub const EXIT_ERROR: i32 = 2;
+pub const EXIT_INCONCLUSIVE: i32 = 3;
+
+const USAGE: &str = "Usage: moxfield-list-verifyer check <moxfield id, url or decklist file> [--ruleset <house|bracket2|bracket3>] [--format <table|json>] [--database <url>]
+
+Lookups are cached in memory unless --database points at a sqlite: or postgres:// cache.
+
+Exit codes: 0 valid, 1 invalid, 2 error, 3 inconclusive";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Table,
+    Json,
+}
+
+struct CheckArgs {
+    deck: String,
+    ruleset: Ruleset,
+    format: OutputFormat,
+    database: Option<String>,
+}
+
+fn parse_args(args: &[String], default_ruleset: Ruleset) -> Result<CheckArgs, String> {
+    let mut deck = None;
+    let mut ruleset = default_ruleset;
+    let mut format = OutputFormat::Table;
+    let mut database = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--ruleset" => {
+                let name = args.next().ok_or("--ruleset requires a value")?;
+                ruleset =
+                    Ruleset::from_name(name).ok_or_else(|| format!("Unknown ruleset {}", name))?;
+            }
+            "--format" => {
+                format = match args.next().map(String::as_str) {
+                    Some("table") => OutputFormat::Table,
+                    Some("json") => OutputFormat::Json,
+                    _ => return Err("--format must be table or json".to_string()),
+                };
+            }
+            "--database" => {
+                let url = args.next().ok_or("--database requires a value")?;
+                if !is_database_url(url) {
+                    return Err(format!(
+                        "--database must be a sqlite: or postgres:// URL, got {:?}",
+                        url
+                    ));
+                }
+                database = Some(url.to_string());
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option {}", flag)),
+            value if deck.is_none() => deck = Some(value.to_string()),
+            value => return Err(format!("Unexpected argument {}", value)),
+        }
+    }
+
+    Ok(CheckArgs {
+        deck: deck.ok_or("Missing deck id or decklist file")?,
+        ruleset,
+        format,
+        database,
+    })
+}
+
+pub async fn run(args: &[String], config: &AppConfig) -> i32 {
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return EXIT_VALID;
+    }
+
+    let args = match parse_args(args, config.default_ruleset) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return EXIT_ERROR;
+        }
+    };
+
+    match check(&args, config).await {
+        Ok(report) => {
+            match args.format {
+                OutputFormat::Json => {
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(&report).unwrap_or_default()
+                    )
+                }
+                OutputFormat::Table => print_table(&report, &args.ruleset),
+            }
+            if report.is_valid {
+                EXIT_VALID
+            } else if report.inconclusive {
+                EXIT_INCONCLUSIVE
+            } else {
+                EXIT_INVALID
+            }
+        }
+        Err(e) => {
+            match args.format {
+                OutputFormat::Json => println!(
+                    "{}",
+                    serde_json::to_string_pretty(&e.to_body(None)).unwrap_or_default()
+                ),
+                OutputFormat::Table => eprintln!("Error: {}", e),
+            }
+            EXIT_ERROR
+        }
+    }
+}
+
+async fn check(args: &CheckArgs, config: &AppConfig) -> Result<Report, AppError> {
+    let upstream = Upstream::new(
+        HttpClient::new(config.http_config()),
+        lookup_cache(args.database.as_deref(), config).await?,
+        config.upstream_config(),
+    );
+
+    let path = Path::new(&args.deck);
+    let list = if path.is_file() {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| AppError::InvalidRequest(format!("Cannot read {}: {}", args.deck, e)))?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| args.deck.clone());
+        parse_decklist(&name, &text)?
+    } else {
+        let id =
+            parse_deck_id(&args.deck).ok_or_else(|| AppError::InvalidDeckId(args.deck.clone()))?;
+        fetch_list(&upstream, &id).await?
+    };
+
+    list.validate(&upstream, &args.ruleset).await
+}
+
+#[cfg(feature = "server")]
+async fn lookup_cache(database: Option<&str>, config: &AppConfig) -> Result<LookupCache, AppError> {
+    use crate::migrator::Migrator;
+    use crate::persistence::CacheEntryStore;
+    use sea_orm::Database;
+    use sea_orm_migration::MigratorTrait;
+
+    let Some(database) = database else {
+        return Ok(LookupCache::new(config.cache_config()));
+    };
+    let conn = Database::connect(database)
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+    Migrator::up(&conn, None)
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
+    Ok(LookupCache::new(config.cache_config()).with_backend(CacheEntryStore::new(conn)))
+}
+
+#[cfg(not(feature = "server"))]
+async fn lookup_cache(database: Option<&str>, config: &AppConfig) -> Result<LookupCache, AppError> {
+    if database.is_some() {
+        return Err(AppError::Configuration(
+            "--database requires the server feature".to_string(),
+        ));
+    }
+    Ok(LookupCache::new(config.cache_config()))
+}
+
+fn print_table(report: &Report, ruleset: &Ruleset) {
+    let verdict = if report.is_valid {
+        "VALID"
+    } else if report.inconclusive {
+        "INCONCLUSIVE"
+    } else {
+        "INVALID"
+    };
+
+    println!("Deck:    {} by {}", report.name, report.author);
+    println!("Ruleset: {}", ruleset.name());
+    println!("Verdict: {}", verdict);
+
+    let mut rows: Vec<(&str, String)> = Vec::new();
+    rows.extend(
+        report
+            .mass_land_denial_cards
+            .iter()
+            .map(|(card, _)| ("Mass Land Denial", card.clone())),
+    );
+    rows.extend(
+        report
+            .non_land_tutors
+            .iter()
+            .map(|(card, _)| ("Non-Land Tutor", card.clone())),
+    );
+    rows.extend(
+        report
+            .commander_tutors
+            .iter()
+            .map(|card| ("Commander Tutor", card.clone())),
+    );
+    rows.extend(
+        report
+            .two_card_combos
+            .iter()
+            .map(|(cards, _)| ("Two-Card Combo", cards.join(" + "))),
+    );
+    rows.extend(
+        report
+            .gamechangers
+            .iter()
+            .map(|card| ("Game Changer", card.clone())),
+    );
+    rows.extend(
+        report
+            .infinite_turns_combos
+            .iter()
+            .map(|cards| ("Infinite Turns", cards.join(" + "))),
+    );
+    rows.extend(
+        report
+            .unknown_cards
+            .iter()
+            .map(|card| ("Unknown Card", card.clone())),
+    );
+    rows.extend(
+        report
+            .incomplete_lookups
+            .iter()
+            .map(|lookup| ("Incomplete Lookup", lookup.clone())),
+    );
+
+    if rows.is_empty() {
+        return;
+    }
+
+    let width = rows
+        .iter()
+        .map(|(category, _)| category.len())
+        .max()
+        .unwrap_or_default()
+        .max("Category".len());
+    println!();
+    println!("{:width$}  Card", "Category");
+    println!("{:-<width$}  {:-<4}", "", "");
+    for (category, cards) in rows {
+        println!("{:width$}  {}", category, cards);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const DECK: &str = r#"{"id":"x1","name":"Mock Deck","format":"commander","visibility":"public","createdByUser":{"userName":"Mocker"},"boards":{"mainboard":{"count":1,"cards":{"x":{"quantity":1,"card":{"id":"x","name":"Sol Ring","legalities":{}}}}},"commanders":{"count":0,"cards":{}}}}"#;
+
+    async fn mock_moxfield() -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let api = format!("http://{}", listener.local_addr().unwrap());
+        let paths = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = paths.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buffer = [0; 4096];
+                let read = socket.read(&mut buffer).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+                let path = request.split(' ').nth(1).unwrap_or_default().to_string();
+                let response = if path == "/v3/decks/all/abc123" {
+                    format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                        DECK.len(),
+                        DECK
+                    )
+                } else {
+                    "HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                        .to_string()
+                };
+                recorded.lock().unwrap().push(path);
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        (api, paths)
+    }
+
+    #[tokio::test]
+    async fn checks_decks_given_as_moxfield_urls() {
+        let (api, paths) = mock_moxfield().await;
+        let config = AppConfig {
+            moxfield_api: api.clone(),
+            scryfall_api: api.clone(),
+            spellbook_api: api,
+            moxfield_user_agent: "test".to_string(),
+            ..AppConfig::default()
+        };
+        let args = parse_args(
+            &["https://moxfield.com/decks/abc123?tab=stats".to_string()],
+            Ruleset::House,
+        )
+        .unwrap();
+
+        let report = check(&args, &config).await.unwrap();
+
+        assert_eq!(report.name, "Mock Deck");
+        assert_eq!(report.deck_id.as_deref(), Some("abc123"));
+        assert_eq!(paths.lock().unwrap()[0], "/v3/decks/all/abc123");
+    }
+
+    #[tokio::test]
+    async fn rejects_arguments_that_are_not_decks() {
+        let args = parse_args(&["not a deck!".to_string()], Ruleset::House).unwrap();
+
+        let result = check(&args, &AppConfig::default()).await;
+
+        assert!(matches!(result, Err(AppError::InvalidDeckId(_))));
+    }
+}
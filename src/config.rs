@@ -0,0 +1,233 @@
+use crate::cache::CacheConfig;
+use crate::errors::AppError;
+use crate::http::{HttpConfig, RateLimit};
+use crate::ruleset::Ruleset;
+use crate::upstream::UpstreamConfig;
+use crate::validation_results::FailMode;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct AppConfig {
+    pub database_url: String,
+    pub moxfield_api: String,
+    pub scryfall_api: String,
+    pub spellbook_api: String,
+    pub moxfield_user_agent: String,
+    pub scryfall_user_agent: String,
+    pub scryfall_cache_ttl_secs: u64,
+    pub spellbook_cache_ttl_secs: u64,
+    pub max_memory_cache_entries: u64,
+    pub http_timeout_secs: u64,
+    pub http_max_retries: u32,
+    pub http_base_backoff_ms: u64,
+    pub moxfield_requests_per_second: f64,
+    pub scryfall_requests_per_second: f64,
+    pub spellbook_requests_per_second: f64,
+    pub batch_concurrency: usize,
+    pub max_batch_concurrency: usize,
+    pub report_max_age_secs: u64,
+    pub default_ruleset: Ruleset,
+    pub validation_fail_mode: FailMode,
+    pub admin_token: Option<String>,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            database_url: "sqlite://data/sqlite.db?mode=rwc".to_string(),
+            moxfield_api: "https://api2.moxfield.com".to_string(),
+            scryfall_api: "https://api.scryfall.com".to_string(),
+            spellbook_api: "https://backend.commanderspellbook.com".to_string(),
+            moxfield_user_agent: String::new(),
+            scryfall_user_agent: "Mozilla/5.0".to_string(),
+            scryfall_cache_ttl_secs: 7 * 24 * 3600,
+            spellbook_cache_ttl_secs: 24 * 3600,
+            max_memory_cache_entries: 50_000,
+            http_timeout_secs: 30,
+            http_max_retries: 3,
+            http_base_backoff_ms: 500,
+            moxfield_requests_per_second: 2.0,
+            scryfall_requests_per_second: 8.0,
+            spellbook_requests_per_second: 5.0,
+            batch_concurrency: 10,
+            max_batch_concurrency: 32,
+            report_max_age_secs: 24 * 3600,
+            default_ruleset: Ruleset::default(),
+            validation_fail_mode: FailMode::default(),
+            admin_token: None,
+        }
+    }
+}
+
+impl AppConfig {
+    pub const ENV_KEYS: [&'static str; 21] = [
+        "DATABASE_URL",
+        "MOXFIELD_API",
+        "SCRYFALL_API",
+        "SPELLBOOK_API",
+        "MOXFIELD_USER_AGENT",
+        "SCRYFALL_USER_AGENT",
+        "SCRYFALL_CACHE_TTL_SECS",
+        "SPELLBOOK_CACHE_TTL_SECS",
+        "MAX_MEMORY_CACHE_ENTRIES",
+        "HTTP_TIMEOUT_SECS",
+        "HTTP_MAX_RETRIES",
+        "HTTP_BASE_BACKOFF_MS",
+        "MOXFIELD_REQUESTS_PER_SECOND",
+        "SCRYFALL_REQUESTS_PER_SECOND",
+        "SPELLBOOK_REQUESTS_PER_SECOND",
+        "BATCH_CONCURRENCY",
+        "MAX_BATCH_CONCURRENCY",
+        "REPORT_MAX_AGE_SECS",
+        "DEFAULT_RULESET",
+        "VALIDATION_FAIL_MODE",
+        "ADMIN_TOKEN",
+    ];
+
+    pub fn validate(&self) -> Result<(), AppError> {
+        let mut problems = Vec::new();
+
+        if !is_database_url(&self.database_url) {
+            problems.push(format!(
+                "database_url must be a sqlite: or postgres:// URL, got {:?} (set DATABASE_URL)",
+                self.database_url
+            ));
+        }
+        if self.moxfield_user_agent.trim().is_empty() {
+            problems.push("moxfield_user_agent is required (set MOXFIELD_USER_AGENT)".to_string());
+        }
+        problems.extend(self.upstream_problems());
+        if self.batch_concurrency == 0 {
+            problems.push("batch_concurrency must be at least 1".to_string());
+        }
+        if self.max_batch_concurrency < self.batch_concurrency {
+            problems.push(format!(
+                "max_batch_concurrency ({}) must not be below batch_concurrency ({})",
+                self.max_batch_concurrency, self.batch_concurrency
+            ));
+        }
+
+        into_result(problems)
+    }
+
+    pub fn validate_cli(&self) -> Result<(), AppError> {
+        into_result(self.upstream_problems())
+    }
+
+    fn upstream_problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        for (key, url) in [
+            ("moxfield_api", &self.moxfield_api),
+            ("scryfall_api", &self.scryfall_api),
+            ("spellbook_api", &self.spellbook_api),
+        ] {
+            if host(url).is_none() {
+                problems.push(format!("{} must be an absolute URL, got {:?}", key, url));
+            }
+        }
+        for (key, secs) in [
+            ("scryfall_cache_ttl_secs", self.scryfall_cache_ttl_secs),
+            ("spellbook_cache_ttl_secs", self.spellbook_cache_ttl_secs),
+            ("http_timeout_secs", self.http_timeout_secs),
+        ] {
+            if secs == 0 {
+                problems.push(format!("{} must be greater than 0", key));
+            }
+        }
+        for (key, rate) in [
+            (
+                "moxfield_requests_per_second",
+                self.moxfield_requests_per_second,
+            ),
+            (
+                "scryfall_requests_per_second",
+                self.scryfall_requests_per_second,
+            ),
+            (
+                "spellbook_requests_per_second",
+                self.spellbook_requests_per_second,
+            ),
+        ] {
+            if !(rate > 0.0 && rate.is_finite()) {
+                problems.push(format!("{} must be greater than 0", key));
+            }
+        }
+
+        problems
+    }
+
+    pub fn batch_concurrency(&self, requested: Option<usize>) -> usize {
+        requested
+            .unwrap_or(self.batch_concurrency)
+            .clamp(1, self.max_batch_concurrency.max(1))
+    }
+
+    pub fn http_config(&self) -> HttpConfig {
+        let host_limits: HashMap<String, RateLimit> = [
+            (&self.moxfield_api, self.moxfield_requests_per_second),
+            (&self.scryfall_api, self.scryfall_requests_per_second),
+            (&self.spellbook_api, self.spellbook_requests_per_second),
+        ]
+        .into_iter()
+        .filter_map(|(url, rate)| {
+            Some((
+                host(url)?,
+                RateLimit {
+                    requests_per_second: rate,
+                    burst: rate.ceil() as u32,
+                },
+            ))
+        })
+        .collect();
+
+        HttpConfig {
+            timeout: Duration::from_secs(self.http_timeout_secs),
+            max_retries: self.http_max_retries,
+            base_backoff: Duration::from_millis(self.http_base_backoff_ms),
+            host_limits,
+            ..HttpConfig::default()
+        }
+    }
+
+    pub fn cache_config(&self) -> CacheConfig {
+        CacheConfig {
+            scryfall_ttl: Duration::from_secs(self.scryfall_cache_ttl_secs),
+            spellbook_ttl: Duration::from_secs(self.spellbook_cache_ttl_secs),
+            max_memory_entries: self.max_memory_cache_entries,
+        }
+    }
+
+    pub fn upstream_config(&self) -> UpstreamConfig {
+        UpstreamConfig {
+            moxfield_api: self.moxfield_api.trim_end_matches('/').to_string(),
+            scryfall_api: self.scryfall_api.trim_end_matches('/').to_string(),
+            spellbook_api: self.spellbook_api.trim_end_matches('/').to_string(),
+            moxfield_user_agent: self.moxfield_user_agent.clone(),
+            scryfall_user_agent: self.scryfall_user_agent.clone(),
+            fail_mode: self.validation_fail_mode,
+        }
+    }
+}
+
+pub fn is_database_url(url: &str) -> bool {
+    ["sqlite:", "postgres://", "postgresql://"]
+        .iter()
+        .any(|scheme| url.starts_with(scheme))
+}
+
+fn into_result(problems: Vec<String>) -> Result<(), AppError> {
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Configuration(problems.join("; ")))
+    }
+}
+
+fn host(url: &str) -> Option<String> {
+    Url::parse(url).ok()?.host_str().map(str::to_string)
+}
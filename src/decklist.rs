@@ -0,0 +1,102 @@
+use crate::errors::AppError;
+use crate::models::{Board, Boards, Card, CardDetails, List, User};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Commanders,
+    Mainboard,
+    Ignored,
+}
+
+fn section_header(line: &str) -> Option<Section> {
+    let header = line
+        .trim_start_matches("//")
+        .trim_end_matches(':')
+        .trim()
+        .to_lowercase();
+    match header.as_str() {
+        "commander" | "commanders" => Some(Section::Commanders),
+        "deck" | "main" | "mainboard" => Some(Section::Mainboard),
+        "sideboard" | "maybeboard" | "considering" | "tokens" => Some(Section::Ignored),
+        _ if line.starts_with("//") => Some(Section::Mainboard),
+        _ => None,
+    }
+}
+
+fn parse_line(line: &str) -> Option<(u32, String)> {
+    let (quantity, rest) = line.split_once(' ')?;
+    let quantity = quantity.trim_end_matches(['x', 'X']).parse().ok()?;
+
+    let mut name = rest.trim();
+    if let Some(index) = name.find(" (") {
+        name = &name[..index];
+    }
+    let name = name.trim_end_matches("*F*").trim();
+
+    (!name.is_empty()).then(|| (quantity, name.to_string()))
+}
+
+fn board(cards: HashMap<String, Card>) -> Board {
+    Board {
+        count: cards.values().map(|card| card.quantity).sum(),
+        cards,
+    }
+}
+
+pub fn parse_decklist(name: &str, text: &str) -> Result<List, AppError> {
+    let mut commanders = HashMap::new();
+    let mut mainboard = HashMap::new();
+    let mut section = Section::Mainboard;
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(header) = section_header(line) {
+            section = header;
+            continue;
+        }
+
+        let (quantity, card_name) = parse_line(line).ok_or_else(|| {
+            AppError::InvalidRequest(format!("Invalid decklist line {}: {}", index + 1, line))
+        })?;
+        let cards = match section {
+            Section::Commanders => &mut commanders,
+            Section::Mainboard => &mut mainboard,
+            Section::Ignored => continue,
+        };
+
+        cards
+            .entry(card_name.clone())
+            .and_modify(|card: &mut Card| card.quantity += quantity)
+            .or_insert(Card {
+                quantity,
+                card: CardDetails {
+                    id: card_name.clone(),
+                    name: card_name,
+                    legalities: HashMap::new(),
+                },
+            });
+    }
+
+    if mainboard.is_empty() && commanders.is_empty() {
+        return Err(AppError::InvalidRequest("Decklist is empty".to_string()));
+    }
+
+    Ok(List {
+        id: name.to_string(),
+        public_id: None,
+        name: name.to_string(),
+        format: "commander".to_string(),
+        visibility: "local".to_string(),
+        created_by_user: User {
+            user_name: "local".to_string(),
+        },
+        boards: Boards {
+            mainboard: board(mainboard),
+            commanders: board(commanders),
+        },
+    })
+}
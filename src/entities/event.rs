@@ -0,0 +1,27 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "event")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub name: String,
+    pub ruleset: String,
+    pub locked_at: Option<DateTimeUtc>,
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::event_player::Entity")]
+    EventPlayer,
+}
+
+impl Related<super::event_player::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EventPlayer.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,38 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "event_player")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub event_id: i32,
+    pub player_name: String,
+    pub deck_id: String,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub locked_deck_list: Option<serde_json::Value>,
+    pub is_valid: Option<bool>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub report: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub changed_after_lock: bool,
+    pub checked_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::event::Entity",
+        from = "Column::EventId",
+        to = "super::event::Column::Id"
+    )]
+    Event,
+}
+
+impl Related<super::event::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Event.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,2 +1,9 @@
+pub mod cache_entry;
+pub mod event;
+pub mod event_player;
+pub mod job;
+pub mod job_deck;
 pub mod prelude;
 pub mod report;
+pub mod report_card;
+pub mod report_finding;
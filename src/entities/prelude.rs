@@ -1 +1,8 @@
+pub use super::cache_entry::Entity as CacheEntry;
+pub use super::event::Entity as Event;
+pub use super::event_player::Entity as EventPlayer;
+pub use super::job::Entity as Job;
+pub use super::job_deck::Entity as JobDeck;
 pub use super::report::Entity as Report;
+pub use super::report_card::Entity as ReportCard;
+pub use super::report_finding::Entity as ReportFinding;
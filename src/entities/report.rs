@@ -9,25 +9,51 @@ pub struct Model {
     pub is_valid: bool,
     pub name: String,
     pub author: String,
-    #[sea_orm(column_type = "Json")]
+    #[sea_orm(column_type = "JsonBinary")]
     pub non_land_tutors: serde_json::Value,
-    #[sea_orm(column_type = "Json")]
+    #[sea_orm(column_type = "JsonBinary")]
     pub mass_land_denial_cards: serde_json::Value,
-    #[sea_orm(column_type = "Json")]
+    #[sea_orm(column_type = "JsonBinary")]
     pub commander_tutors: serde_json::Value,
-    #[sea_orm(column_type = "Json")]
+    #[sea_orm(column_type = "JsonBinary")]
     pub two_card_combos: serde_json::Value,
-    #[sea_orm(column_type = "Json")]
+    #[sea_orm(column_type = "JsonBinary")]
     pub gamechangers: serde_json::Value,
-    #[sea_orm(column_type = "Json")]
+    #[sea_orm(column_type = "JsonBinary")]
     pub infinite_turns_combos: serde_json::Value,
-    #[sea_orm(column_type = "Json")]
+    #[sea_orm(column_type = "JsonBinary")]
     pub combos: serde_json::Value,
-    #[sea_orm(column_type = "Json")]
+    #[sea_orm(column_type = "JsonBinary")]
     pub deck_list: serde_json::Value,
+    pub inconclusive: bool,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub incomplete_lookups: Option<serde_json::Value>,
+    pub created_at: Option<DateTimeUtc>,
+    pub ruleset: Option<String>,
+    pub content_hash: Option<String>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub unknown_cards: Option<serde_json::Value>,
+    pub deck_id: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::report_finding::Entity")]
+    ReportFinding,
+    #[sea_orm(has_many = "super::report_card::Entity")]
+    ReportCard,
+}
+
+impl Related<super::report_finding::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ReportFinding.def()
+    }
+}
+
+impl Related<super::report_card::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ReportCard.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -1,40 +1,93 @@
-use rocket::Request;
-use rocket::http::{ContentType, Status};
-use rocket::response::{Responder, Response, Result};
-use std::io::Cursor;
+use serde::Serialize;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum AppError {
     #[error("Moxfield API error: {0}")]
     MoxfieldApiError(#[from] reqwest::Error),
-    #[error("Scryfall API error: {0}")]
-    ScryfallApiError(String),
+    #[error("Deck {0} not found on Moxfield")]
+    DeckNotFound(String),
+    #[error("Deck {0} is private")]
+    DeckPrivate(String),
+    #[error("Invalid deck id: {0}")]
+    InvalidDeckId(String),
+    #[error("Moxfield rate limit exceeded")]
+    MoxfieldRateLimited { retry_after: Option<u64> },
     #[error("Spellbook API error: {0}")]
     SpellbookApiError(String),
-    #[error("Environment variable missing: {0}")]
-    EnvVarMissing(#[from] std::env::VarError),
+    #[error("Invalid configuration: {0}")]
+    Configuration(String),
+    #[error("Author {0} not found")]
+    AuthorNotFound(String),
+    #[error("Event {0} not found")]
+    EventNotFound(i32),
+    #[error("Event {0} is locked")]
+    EventLocked(i32),
+    #[error("Job {0} not found")]
+    JobNotFound(i32),
+    #[error("Missing or invalid admin token")]
+    Unauthorized,
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
     #[error("Internal error: {0}")]
     Internal(String),
 }
 
-#[rocket::async_trait]
-impl<'r> Responder<'r, 'static> for AppError {
-    fn respond_to(self, _: &'r Request<'_>) -> Result<'static> {
-        let status = match self {
-            AppError::MoxfieldApiError(_) => Status::BadGateway,
-            AppError::ScryfallApiError(_) => Status::BadGateway,
-            AppError::SpellbookApiError(_) => Status::BadGateway,
-            AppError::EnvVarMissing(_) => Status::InternalServerError,
-            AppError::Internal(_) => Status::InternalServerError,
-        };
+#[derive(Serialize, Debug, Clone)]
+pub struct ErrorBody {
+    pub code: &'static str,
+    pub message: String,
+    pub upstream: Option<&'static str>,
+    pub retry_after: Option<u64>,
+    pub request_id: Option<String>,
+}
+
+impl AppError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::MoxfieldApiError(e) if e.is_timeout() => "upstream_timeout",
+            AppError::MoxfieldApiError(_) => "upstream_error",
+            AppError::DeckNotFound(_) => "deck_not_found",
+            AppError::DeckPrivate(_) => "deck_private",
+            AppError::InvalidDeckId(_) => "invalid_deck_id",
+            AppError::MoxfieldRateLimited { .. } => "rate_limited",
+            AppError::SpellbookApiError(_) => "upstream_error",
+            AppError::Configuration(_) => "configuration_error",
+            AppError::AuthorNotFound(_) => "author_not_found",
+            AppError::EventNotFound(_) => "event_not_found",
+            AppError::EventLocked(_) => "event_locked",
+            AppError::JobNotFound(_) => "job_not_found",
+            AppError::Unauthorized => "unauthorized",
+            AppError::InvalidRequest(_) => "invalid_request",
+            AppError::Internal(_) => "internal_error",
+        }
+    }
 
-        let body = format!("{{\"error\": \"{}\"}}", self);
+    pub fn upstream(&self) -> Option<&'static str> {
+        match self {
+            AppError::MoxfieldApiError(_)
+            | AppError::DeckNotFound(_)
+            | AppError::DeckPrivate(_)
+            | AppError::MoxfieldRateLimited { .. } => Some("moxfield"),
+            AppError::SpellbookApiError(_) => Some("spellbook"),
+            _ => None,
+        }
+    }
+
+    pub fn retry_after(&self) -> Option<u64> {
+        match self {
+            AppError::MoxfieldRateLimited { retry_after } => *retry_after,
+            _ => None,
+        }
+    }
 
-        Response::build()
-            .status(status)
-            .header(ContentType::JSON)
-            .sized_body(body.len(), Cursor::new(body))
-            .ok()
+    pub fn to_body(&self, request_id: Option<String>) -> ErrorBody {
+        ErrorBody {
+            code: self.code(),
+            message: self.to_string(),
+            upstream: self.upstream(),
+            retry_after: self.retry_after(),
+            request_id,
+        }
     }
 }
@@ -0,0 +1,245 @@
+use crate::config::AppConfig;
+use crate::entities::{event, event_player};
+use crate::errors::AppError;
+use crate::models::{CardListUnit, Report};
+use crate::moxfield::{fetch_list, parse_deck_id};
+use crate::persistence::{EventStore, HistoryStore};
+use crate::ruleset::Ruleset;
+use crate::upstream::Upstream;
+use futures::stream::{self, StreamExt};
+use rocket::{State, serde::json::Json};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Debug)]
+pub struct NewEvent {
+    pub name: String,
+    pub ruleset: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct NewPlayer {
+    pub player_name: String,
+    pub deck_url: String,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayerStatus {
+    Pending,
+    Valid,
+    Invalid,
+    Inconclusive,
+    Error,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PlayerSummary {
+    pub id: i32,
+    pub player_name: String,
+    pub deck_id: String,
+    pub status: PlayerStatus,
+    pub locked: bool,
+    pub changed_after_lock: bool,
+    pub error: Option<String>,
+    pub checked_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct EventDashboard {
+    pub id: i32,
+    pub name: String,
+    pub ruleset: String,
+    pub locked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub passed: usize,
+    pub failed: usize,
+    pub inconclusive: usize,
+    pub errored: usize,
+    pub pending: usize,
+    pub changed_after_lock: usize,
+    pub players: Vec<PlayerSummary>,
+}
+
+impl From<event_player::Model> for PlayerSummary {
+    fn from(player: event_player::Model) -> Self {
+        let inconclusive = player
+            .report
+            .as_ref()
+            .and_then(|report| report.get("inconclusive"))
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        let status = match (player.is_valid, &player.error) {
+            (_, Some(_)) => PlayerStatus::Error,
+            (Some(true), None) => PlayerStatus::Valid,
+            (Some(false), None) if inconclusive => PlayerStatus::Inconclusive,
+            (Some(false), None) => PlayerStatus::Invalid,
+            (None, None) => PlayerStatus::Pending,
+        };
+
+        Self {
+            id: player.id,
+            player_name: player.player_name,
+            deck_id: player.deck_id,
+            status,
+            locked: player.locked_deck_list.is_some(),
+            changed_after_lock: player.changed_after_lock,
+            error: player.error,
+            checked_at: player.checked_at,
+        }
+    }
+}
+
+async fn build_dashboard(
+    store: &EventStore,
+    event: event::Model,
+) -> Result<EventDashboard, AppError> {
+    let players: Vec<PlayerSummary> = store
+        .get_players(event.id)
+        .await?
+        .into_iter()
+        .map(PlayerSummary::from)
+        .collect();
+
+    let count = |status: PlayerStatus| players.iter().filter(|p| p.status == status).count();
+
+    Ok(EventDashboard {
+        id: event.id,
+        name: event.name,
+        ruleset: event.ruleset,
+        locked_at: event.locked_at,
+        passed: count(PlayerStatus::Valid),
+        failed: count(PlayerStatus::Invalid),
+        inconclusive: count(PlayerStatus::Inconclusive),
+        errored: count(PlayerStatus::Error),
+        pending: count(PlayerStatus::Pending),
+        changed_after_lock: players.iter().filter(|p| p.changed_after_lock).count(),
+        players,
+    })
+}
+
+fn event_ruleset(event: &event::Model) -> Result<Ruleset, AppError> {
+    Ruleset::from_name(&event.ruleset)
+        .ok_or_else(|| AppError::Internal(format!("Unknown ruleset {}", event.ruleset)))
+}
+
+#[post("/events", data = "<new_event>")]
+pub async fn create_event(
+    new_event: Json<NewEvent>,
+    store: &State<EventStore>,
+    config: &State<AppConfig>,
+) -> Result<Json<EventDashboard>, AppError> {
+    let new_event = new_event.into_inner();
+    let ruleset = match new_event.ruleset {
+        Some(name) => Ruleset::from_name(&name)
+            .ok_or_else(|| AppError::InvalidRequest(format!("Unknown ruleset {}", name)))?,
+        None => config.default_ruleset,
+    };
+
+    let event = store
+        .create_event(new_event.name, ruleset.name().to_string())
+        .await?;
+
+    Ok(Json(build_dashboard(store, event).await?))
+}
+
+#[get("/events/<event_id>")]
+pub async fn get_event(
+    event_id: i32,
+    store: &State<EventStore>,
+) -> Result<Json<EventDashboard>, AppError> {
+    let event = store.get_event(event_id).await?;
+    Ok(Json(build_dashboard(store, event).await?))
+}
+
+#[post("/events/<event_id>/players", data = "<new_player>")]
+pub async fn register_player(
+    event_id: i32,
+    new_player: Json<NewPlayer>,
+    store: &State<EventStore>,
+) -> Result<Json<PlayerSummary>, AppError> {
+    let new_player = new_player.into_inner();
+    let deck_id = parse_deck_id(&new_player.deck_url).ok_or_else(|| {
+        AppError::InvalidRequest(format!("Invalid deck URL {}", new_player.deck_url))
+    })?;
+
+    let player = store
+        .add_player(event_id, new_player.player_name, deck_id)
+        .await?;
+
+    Ok(Json(player.into()))
+}
+
+#[post("/events/<event_id>/lock")]
+pub async fn lock_event(
+    event_id: i32,
+    upstream: &State<Upstream>,
+    store: &State<EventStore>,
+    config: &State<AppConfig>,
+) -> Result<Json<EventDashboard>, AppError> {
+    // Registration closes before any deck is fetched; locking again retries failed snapshots.
+    let (event, players) = store.lock_event(event_id).await?;
+    let snapshots = stream::iter(players)
+        .map(|player| async move {
+            let snapshot = fetch_list(upstream, &player.deck_id)
+                .await
+                .map(|list| list.normalized_cards())
+                .map_err(|e| e.to_string());
+            (player, snapshot)
+        })
+        .buffer_unordered(config.batch_concurrency)
+        .collect::<Vec<_>>()
+        .await;
+    store.lock_players(snapshots).await?;
+
+    Ok(Json(build_dashboard(store, event).await?))
+}
+
+#[post("/events/<event_id>/check")]
+pub async fn check_event(
+    event_id: i32,
+    upstream: &State<Upstream>,
+    store: &State<EventStore>,
+    history: &State<HistoryStore>,
+    config: &State<AppConfig>,
+) -> Result<Json<EventDashboard>, AppError> {
+    let event = store.get_event(event_id).await?;
+    let ruleset = event_ruleset(&event)?;
+
+    let players = store.get_players(event.id).await?;
+    let outcomes = stream::iter(players)
+        .map(|player| async move {
+            let outcome = check_player(&player, upstream, history, &ruleset)
+                .await
+                .map_err(|e| e.to_string());
+            (player, outcome)
+        })
+        .buffer_unordered(config.batch_concurrency)
+        .collect::<Vec<_>>()
+        .await;
+    store.record_checks(outcomes).await?;
+
+    Ok(Json(build_dashboard(store, event).await?))
+}
+
+async fn check_player(
+    player: &event_player::Model,
+    upstream: &Upstream,
+    history: &HistoryStore,
+    ruleset: &Ruleset,
+) -> Result<(Report, bool), AppError> {
+    let list = fetch_list(upstream, &player.deck_id).await?;
+    let changed_after_lock = match &player.locked_deck_list {
+        Some(locked) => {
+            let locked: Vec<CardListUnit> =
+                serde_json::from_value(locked.clone()).unwrap_or_default();
+            locked != list.normalized_cards()
+        }
+        None => false,
+    };
+
+    if let Some(report) = history.find_recent(&list, ruleset).await? {
+        return Ok((report, changed_after_lock));
+    }
+    let report = list.validate(upstream, ruleset).await?;
+    history.save(report.clone()).await?;
+    Ok((report, changed_after_lock))
+}
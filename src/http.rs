@@ -0,0 +1,469 @@
+use chrono::{DateTime, Utc};
+use reqwest::header::RETRY_AFTER;
+use reqwest::{IntoUrl, RequestBuilder, Response, StatusCode};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+pub const SCRYFALL_HOST: &str = "api.scryfall.com";
+pub const MOXFIELD_HOST: &str = "api2.moxfield.com";
+pub const SPELLBOOK_HOST: &str = "backend.commanderspellbook.com";
+
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimit {
+    pub requests_per_second: f64,
+    pub burst: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpConfig {
+    pub timeout: Duration,
+    pub max_retries: u32,
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+    pub default_limit: RateLimit,
+    pub host_limits: HashMap<String, RateLimit>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        let host_limits = HashMap::from([
+            (
+                SCRYFALL_HOST.to_string(),
+                RateLimit {
+                    requests_per_second: 8.0,
+                    burst: 8,
+                },
+            ),
+            (
+                MOXFIELD_HOST.to_string(),
+                RateLimit {
+                    requests_per_second: 2.0,
+                    burst: 4,
+                },
+            ),
+            (
+                SPELLBOOK_HOST.to_string(),
+                RateLimit {
+                    requests_per_second: 5.0,
+                    burst: 5,
+                },
+            ),
+        ]);
+
+        Self {
+            timeout: Duration::from_secs(30),
+            max_retries: 3,
+            base_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            default_limit: RateLimit {
+                requests_per_second: 10.0,
+                burst: 10,
+            },
+            host_limits,
+        }
+    }
+}
+
+impl HttpConfig {
+    fn limit_for(&self, host: &str) -> RateLimit {
+        self.host_limits
+            .get(host)
+            .copied()
+            .unwrap_or(self.default_limit)
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.base_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff)
+    }
+}
+
+struct TokenBucket {
+    limit: RateLimit,
+    tokens: f64,
+    last_refill: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            tokens: limit.burst as f64,
+            last_refill: Instant::now(),
+            paused_until: None,
+        }
+    }
+
+    fn reserve(&mut self) -> Duration {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * self.limit.requests_per_second).min(self.limit.burst as f64);
+        self.last_refill = now;
+
+        self.tokens -= 1.0;
+        let wait = if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.limit.requests_per_second)
+        };
+
+        match self.paused_until {
+            Some(until) if until > now => wait.max(until - now),
+            _ => wait,
+        }
+    }
+
+    fn pause(&mut self, duration: Duration) {
+        let until = Instant::now() + duration;
+        if self.paused_until.is_none_or(|current| current < until) {
+            self.paused_until = Some(until);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct HttpClient {
+    inner: reqwest::Client,
+    config: Arc<HttpConfig>,
+    buckets: Arc<Mutex<HashMap<String, TokenBucket>>>,
+}
+
+impl HttpClient {
+    pub fn new(config: HttpConfig) -> Self {
+        let inner = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self {
+            inner,
+            config: Arc::new(config),
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn get<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.inner.get(url)
+    }
+
+    pub fn post<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.inner.post(url)
+    }
+
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response, reqwest::Error> {
+        let request = request.build()?;
+        let host = request.url().host_str().unwrap_or_default().to_string();
+
+        let mut attempt = 0;
+        loop {
+            let Some(current) = request.try_clone() else {
+                self.acquire(&host).await;
+                return self.inner.execute(request).await;
+            };
+
+            self.acquire(&host).await;
+            let result = self.inner.execute(current).await;
+            if attempt >= self.config.max_retries {
+                return result;
+            }
+
+            let delay = match &result {
+                Ok(response) if is_retryable(response.status()) => {
+                    let delay = retry_after(response).unwrap_or(self.config.backoff(attempt));
+                    if delay > self.config.max_backoff {
+                        return result;
+                    }
+                    if response.status() == StatusCode::TOO_MANY_REQUESTS {
+                        self.pause(&host, delay);
+                    }
+                    delay
+                }
+                Err(e) if e.is_timeout() || e.is_connect() => self.config.backoff(attempt),
+                _ => return result,
+            };
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    async fn acquire(&self, host: &str) {
+        let wait = {
+            let mut buckets = self.buckets.lock().unwrap();
+            buckets
+                .entry(host.to_string())
+                .or_insert_with(|| TokenBucket::new(self.config.limit_for(host)))
+                .reserve()
+        };
+
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    fn pause(&self, host: &str, duration: Duration) {
+        let mut buckets = self.buckets.lock().unwrap();
+        buckets
+            .entry(host.to_string())
+            .or_insert_with(|| TokenBucket::new(self.config.limit_for(host)))
+            .pause(duration);
+    }
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+pub fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?;
+    parse_retry_after(value, Utc::now())
+}
+
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    struct MockServer {
+        port: u16,
+        hits: Arc<Mutex<Vec<Instant>>>,
+    }
+
+    impl MockServer {
+        async fn start(responses: Vec<&'static str>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let hits = Arc::new(Mutex::new(Vec::new()));
+
+            let recorded = hits.clone();
+            tokio::spawn(async move {
+                for index in 0.. {
+                    let Ok((mut socket, _)) = listener.accept().await else {
+                        return;
+                    };
+                    let mut request = Vec::new();
+                    let mut buffer = [0; 1024];
+                    while !request.ends_with(b"\r\n\r\n") {
+                        match socket.read(&mut buffer).await {
+                            Ok(0) | Err(_) => break,
+                            Ok(read) => request.extend_from_slice(&buffer[..read]),
+                        }
+                    }
+                    recorded.lock().unwrap().push(Instant::now());
+
+                    let status = responses[index.min(responses.len() - 1)];
+                    let (status, headers) = status.split_once('|').unwrap_or((status, ""));
+                    let response = format!(
+                        "HTTP/1.1 {}\r\n{}Content-Length: 0\r\nConnection: close\r\n\r\n",
+                        status, headers
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                }
+            });
+
+            Self { port, hits }
+        }
+
+        fn url(&self, host: &str) -> String {
+            format!("http://{}:{}/", host, self.port)
+        }
+
+        fn hits(&self) -> Vec<Instant> {
+            self.hits.lock().unwrap().clone()
+        }
+    }
+
+    fn client(max_retries: u32, requests_per_second: f64) -> HttpClient {
+        let limit = RateLimit {
+            requests_per_second,
+            burst: 1,
+        };
+        HttpClient::new(HttpConfig {
+            timeout: Duration::from_secs(5),
+            max_retries,
+            base_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_secs(2),
+            default_limit: limit,
+            host_limits: HashMap::from([("127.0.0.1".to_string(), limit)]),
+        })
+    }
+
+    fn gaps(hits: &[Instant]) -> Vec<Duration> {
+        hits.windows(2).map(|pair| pair[1] - pair[0]).collect()
+    }
+
+    #[tokio::test]
+    async fn paces_requests_per_host() {
+        let server = MockServer::start(vec!["200 OK"]).await;
+        let client = client(0, 10.0);
+
+        for _ in 0..4 {
+            let response = client.send(client.get(server.url("127.0.0.1"))).await;
+            assert_eq!(response.unwrap().status(), StatusCode::OK);
+        }
+        let paced = server.hits();
+        assert!(
+            gaps(&paced)
+                .iter()
+                .all(|gap| *gap >= Duration::from_millis(80))
+        );
+
+        let started = Instant::now();
+        client
+            .send(client.get(server.url("localhost")))
+            .await
+            .unwrap();
+        assert!(started.elapsed() < Duration::from_millis(80));
+    }
+
+    #[tokio::test]
+    async fn retries_rate_limits_and_server_errors() {
+        let server = MockServer::start(vec![
+            "503 Service Unavailable",
+            "429 Too Many Requests|Retry-After: 0\r\n",
+            "500 Internal Server Error",
+            "200 OK",
+        ])
+        .await;
+        let client = client(3, 1000.0);
+
+        let response = client
+            .send(client.get(server.url("127.0.0.1")))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(server.hits().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn backs_off_exponentially() {
+        let server = MockServer::start(vec![
+            "502 Bad Gateway",
+            "502 Bad Gateway",
+            "502 Bad Gateway",
+            "200 OK",
+        ])
+        .await;
+        let client = client(3, 1000.0);
+
+        client
+            .send(client.get(server.url("127.0.0.1")))
+            .await
+            .unwrap();
+
+        let gaps = gaps(&server.hits());
+        assert_eq!(gaps.len(), 3);
+        for (gap, expected) in gaps.iter().zip([10, 20, 40]) {
+            assert!(*gap >= Duration::from_millis(expected));
+        }
+    }
+
+    #[tokio::test]
+    async fn honours_retry_after() {
+        let server =
+            MockServer::start(vec!["429 Too Many Requests|Retry-After: 1\r\n", "200 OK"]).await;
+        let client = client(3, 1000.0);
+
+        let response = client
+            .send(client.get(server.url("127.0.0.1")))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(gaps(&server.hits())[0] >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn fails_fast_when_retry_after_exceeds_max_backoff() {
+        let server = MockServer::start(vec![
+            "429 Too Many Requests|Retry-After: 86400\r\n",
+            "200 OK",
+        ])
+        .await;
+        let client = client(3, 1000.0);
+        let started = Instant::now();
+
+        let response = client
+            .send(client.get(server.url("127.0.0.1")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(server.hits().len(), 1);
+
+        let response = client
+            .send(client.get(server.url("127.0.0.1")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_retry_cap() {
+        let server = MockServer::start(vec!["500 Internal Server Error"]).await;
+        let client = client(2, 1000.0);
+
+        let response = client
+            .send(client.get(server.url("127.0.0.1")))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(server.hits().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_client_errors() {
+        let server = MockServer::start(vec!["404 Not Found"]).await;
+        let client = client(3, 1000.0);
+
+        let response = client
+            .send(client.get(server.url("127.0.0.1")))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(server.hits().len(), 1);
+    }
+
+    #[test]
+    fn parses_retry_after_seconds_and_dates() {
+        let now = Utc.with_ymd_and_hms(2015, 10, 21, 7, 28, 0).unwrap();
+
+        assert_eq!(
+            parse_retry_after(" 120 ", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+}
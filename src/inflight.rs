@@ -0,0 +1,405 @@
+use crate::errors::AppError;
+use crate::moxfield::fetch_list;
+use crate::persistence::HistoryStore;
+use crate::progress::{ProgressEvent, ProgressMessage, ProgressTracker};
+use crate::ruleset::Ruleset;
+use crate::upstream::Upstream;
+use futures::FutureExt;
+use std::collections::HashMap;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tokio::task::AbortHandle;
+
+const RUN_RETENTION: Duration = Duration::from_secs(60);
+
+type RunKey = (String, Ruleset);
+type Runs = Arc<Mutex<HashMap<RunKey, Run>>>;
+
+struct Run {
+    generation: u64,
+    tracker: ProgressTracker,
+    watchers: usize,
+    task: Option<AbortHandle>,
+    finished: bool,
+    idle_since: Option<Instant>,
+}
+
+fn mark_idle(runs: &Runs, run: &mut Run, key: &RunKey, retention: Duration) {
+    run.idle_since = Some(Instant::now());
+
+    let Ok(handle) = tokio::runtime::Handle::try_current() else {
+        return;
+    };
+    let runs = runs.clone();
+    let key = key.clone();
+    let generation = run.generation;
+    handle.spawn(async move {
+        tokio::time::sleep(retention).await;
+
+        let mut runs = runs.lock().unwrap();
+        let expired = runs.get(&key).is_some_and(|run| {
+            run.generation == generation
+                && run.watchers == 0
+                && run
+                    .idle_since
+                    .is_some_and(|since| since.elapsed() >= retention)
+        });
+        if !expired {
+            return;
+        }
+        if let Some(task) = runs.remove(&key).and_then(|run| run.task) {
+            task.abort();
+        }
+    });
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resume {
+    Never,
+    Allowed,
+    Requested,
+}
+
+pub struct Watcher {
+    runs: Runs,
+    key: RunKey,
+    generation: u64,
+    retention: Option<Duration>,
+    released: bool,
+}
+
+impl Watcher {
+    pub fn cancel(mut self) {
+        self.release(None);
+    }
+
+    fn release(&mut self, retention: Option<Duration>) {
+        if std::mem::replace(&mut self.released, true) {
+            return;
+        }
+        let Ok(mut runs) = self.runs.lock() else {
+            return;
+        };
+        let Some(run) = runs
+            .get_mut(&self.key)
+            .filter(|run| run.generation == self.generation)
+        else {
+            return;
+        };
+
+        run.watchers -= 1;
+        if run.watchers > 0 {
+            return;
+        }
+        match retention {
+            Some(retention) => mark_idle(&self.runs, run, &self.key, retention),
+            None => {
+                if let Some(task) = &run.task {
+                    task.abort();
+                }
+                runs.remove(&self.key);
+            }
+        }
+    }
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        self.release(self.retention);
+    }
+}
+
+pub struct Subscription {
+    pub joined: bool,
+    pub replay: Vec<ProgressMessage>,
+    pub receiver: broadcast::Receiver<ProgressMessage>,
+    pub watcher: Watcher,
+}
+
+#[derive(Clone)]
+pub struct InFlightValidations {
+    upstream: Upstream,
+    store: HistoryStore,
+    runs: Runs,
+    next_generation: Arc<AtomicU64>,
+    retention: Duration,
+}
+
+impl InFlightValidations {
+    pub fn new(upstream: Upstream, store: HistoryStore) -> Self {
+        Self {
+            upstream,
+            store,
+            runs: Arc::new(Mutex::new(HashMap::new())),
+            next_generation: Arc::new(AtomicU64::new(0)),
+            retention: RUN_RETENTION,
+        }
+    }
+
+    pub fn with_retention(mut self, retention: Duration) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    pub fn join(
+        &self,
+        id: String,
+        ruleset: Ruleset,
+        request_id: String,
+        resume: Resume,
+    ) -> Subscription {
+        let key = (id, ruleset);
+        let mut runs = self.runs.lock().unwrap();
+
+        if let Some(run) = runs
+            .get_mut(&key)
+            .filter(|run| resume == Resume::Requested || !run.finished)
+        {
+            run.watchers += 1;
+            run.idle_since = None;
+            let (replay, receiver) = run.tracker.replay();
+            return Subscription {
+                joined: true,
+                replay,
+                receiver,
+                watcher: self.watcher(key, run.generation, resume),
+            };
+        }
+
+        let generation = self.next_generation.fetch_add(1, Ordering::SeqCst);
+        let tracker = ProgressTracker::new(ruleset.validators().len()).with_replay();
+        let (replay, receiver) = tracker.replay();
+        tracker.send(ProgressEvent::Started { ruleset });
+
+        runs.insert(
+            key.clone(),
+            Run {
+                generation,
+                tracker: tracker.clone(),
+                watchers: 1,
+                task: None,
+                finished: false,
+                idle_since: None,
+            },
+        );
+        let task = tokio::spawn(
+            self.clone()
+                .run(key.clone(), generation, tracker, request_id),
+        );
+        if let Some(run) = runs.get_mut(&key) {
+            run.task = Some(task.abort_handle());
+        }
+
+        Subscription {
+            joined: false,
+            replay,
+            receiver,
+            watcher: self.watcher(key, generation, resume),
+        }
+    }
+
+    fn watcher(&self, key: RunKey, generation: u64, resume: Resume) -> Watcher {
+        Watcher {
+            runs: self.runs.clone(),
+            key,
+            generation,
+            retention: (resume != Resume::Never).then_some(self.retention),
+            released: false,
+        }
+    }
+
+    async fn run(self, key: RunKey, generation: u64, tracker: ProgressTracker, request_id: String) {
+        let (id, ruleset) = &key;
+        let validation = async {
+            let list = fetch_list(&self.upstream, id).await?;
+            if let Some(report) = self.store.find_recent(&list, ruleset).await? {
+                return Ok(report);
+            }
+
+            let report = list
+                .validate_with_progress(&self.upstream, ruleset, Some(&tracker))
+                .await?;
+
+            self.store.save(report.clone()).await?;
+            Ok::<_, AppError>(report)
+        };
+
+        match AssertUnwindSafe(validation).catch_unwind().await {
+            Ok(Ok(report)) => tracker.complete(report),
+            Ok(Err(e)) => tracker.fail(e.to_body(Some(request_id))),
+            Err(_) => tracker.fail(
+                AppError::Internal("Validation task panicked".to_string())
+                    .to_body(Some(request_id)),
+            ),
+        }
+
+        let mut runs = self.runs.lock().unwrap();
+        if let Some(run) = runs
+            .get_mut(&key)
+            .filter(|run| run.generation == generation)
+        {
+            run.finished = true;
+            run.task = None;
+            if run.watchers == 0 {
+                mark_idle(&self.runs, run, &key, self.retention);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{CacheConfig, LookupCache};
+    use crate::http::{HttpClient, HttpConfig};
+    use crate::upstream::UpstreamConfig;
+    use tokio::net::TcpListener;
+
+    async fn validations(moxfield_api: String, moxfield_user_agent: &str) -> InFlightValidations {
+        let upstream = Upstream::new(
+            HttpClient::new(HttpConfig::default()),
+            LookupCache::new(CacheConfig::default()),
+            UpstreamConfig {
+                moxfield_api,
+                scryfall_api: String::new(),
+                spellbook_api: String::new(),
+                moxfield_user_agent: moxfield_user_agent.to_string(),
+                scryfall_user_agent: String::new(),
+                fail_mode: Default::default(),
+            },
+        );
+        let conn = sea_orm::Database::connect("sqlite::memory:").await.unwrap();
+        InFlightValidations::new(upstream, HistoryStore::new(conn))
+    }
+
+    async fn until_terminal(subscription: &mut Subscription) -> Vec<ProgressMessage> {
+        let mut messages = subscription.replay.clone();
+        while !messages.last().is_some_and(ProgressMessage::is_terminal) {
+            messages.push(subscription.receiver.recv().await.unwrap());
+        }
+        messages
+    }
+
+    #[tokio::test]
+    async fn reconnect_resumes_finished_run() {
+        let inflight = validations(String::new(), "").await;
+
+        let mut first = inflight.join(
+            "abc123".into(),
+            Ruleset::House,
+            "r1".into(),
+            Resume::Allowed,
+        );
+        assert!(!first.joined);
+        let delivered = until_terminal(&mut first).await;
+        drop(first);
+        tokio::task::yield_now().await;
+
+        let resumed = inflight.join(
+            "abc123".into(),
+            Ruleset::House,
+            "r2".into(),
+            Resume::Requested,
+        );
+        assert!(resumed.joined);
+        let seqs =
+            |messages: &[ProgressMessage]| messages.iter().map(|m| m.seq).collect::<Vec<_>>();
+        assert_eq!(seqs(&resumed.replay), seqs(&delivered));
+
+        let fresh = inflight.join(
+            "abc123".into(),
+            Ruleset::House,
+            "r3".into(),
+            Resume::Allowed,
+        );
+        assert!(!fresh.joined);
+    }
+
+    async fn hanging_moxfield() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let api = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                sockets.push(socket);
+            }
+        });
+        api
+    }
+
+    #[tokio::test]
+    async fn orphaned_run_survives_until_retention_expires() {
+        let inflight = validations(hanging_moxfield().await, "test")
+            .await
+            .with_retention(Duration::from_millis(200));
+
+        let first = inflight.join(
+            "abc123".into(),
+            Ruleset::House,
+            "r1".into(),
+            Resume::Allowed,
+        );
+        drop(first);
+
+        let rejoined = inflight.join(
+            "abc123".into(),
+            Ruleset::House,
+            "r2".into(),
+            Resume::Requested,
+        );
+        assert!(rejoined.joined);
+        drop(rejoined);
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let restarted = inflight.join(
+            "abc123".into(),
+            Ruleset::House,
+            "r3".into(),
+            Resume::Requested,
+        );
+        assert!(!restarted.joined);
+    }
+
+    #[tokio::test]
+    async fn cancel_aborts_run_without_retention() {
+        let inflight = validations(hanging_moxfield().await, "test").await;
+
+        let subscription = inflight.join(
+            "abc123".into(),
+            Ruleset::House,
+            "r1".into(),
+            Resume::Allowed,
+        );
+        let task = inflight.runs.lock().unwrap()[&("abc123".to_string(), Ruleset::House)]
+            .task
+            .clone()
+            .unwrap();
+        subscription.watcher.cancel();
+
+        assert!(inflight.runs.lock().unwrap().is_empty());
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while !task.is_finished() {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("cancelled validation was not aborted");
+    }
+
+    #[tokio::test]
+    async fn unresumable_watcher_aborts_run_when_it_leaves() {
+        let inflight = validations(hanging_moxfield().await, "test").await;
+
+        let first = inflight.join("abc123".into(), Ruleset::House, "r1".into(), Resume::Never);
+        let second = inflight.join("abc123".into(), Ruleset::House, "r2".into(), Resume::Never);
+        assert!(second.joined);
+
+        drop(first);
+        assert_eq!(inflight.runs.lock().unwrap().len(), 1);
+        drop(second);
+        assert!(inflight.runs.lock().unwrap().is_empty());
+    }
+}
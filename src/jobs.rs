@@ -0,0 +1,300 @@
+use crate::config::AppConfig;
+use crate::entities::job;
+use crate::entities::job_deck::{self, JobDeckStatus};
+use crate::errors::AppError;
+use crate::moxfield::fetch_list;
+use crate::persistence::{HistoryStore, JobStore};
+use crate::progress::{PROGRESS_CHANNEL_CAPACITY, ProgressEvent, ProgressMessage, ProgressTracker};
+use crate::request_id::RequestId;
+use crate::ruleset::Ruleset;
+use crate::upstream::Upstream;
+use futures::stream::{self, StreamExt};
+use rocket::futures::SinkExt;
+use rocket::response::status::Accepted;
+use rocket::{State, serde::json::Json};
+use rocket_ws::{Channel, Message, WebSocket};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+#[derive(Deserialize, Debug)]
+pub struct NewJob {
+    pub ids: Vec<String>,
+    pub ruleset: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct JobStatus {
+    pub id: i32,
+    pub ruleset: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub error: Option<String>,
+    pub total: usize,
+    pub pending: usize,
+    pub running: usize,
+    pub done: usize,
+    pub failed: usize,
+    pub inconclusive: usize,
+    pub decks: Vec<job_deck::Model>,
+}
+
+#[derive(Clone)]
+pub struct JobRunner {
+    upstream: Upstream,
+    store: JobStore,
+    history: HistoryStore,
+    concurrency: usize,
+    channels: Arc<Mutex<HashMap<i32, broadcast::Sender<ProgressMessage>>>>,
+}
+
+impl JobRunner {
+    pub fn new(
+        upstream: Upstream,
+        store: JobStore,
+        history: HistoryStore,
+        concurrency: usize,
+    ) -> Self {
+        Self {
+            upstream,
+            store,
+            history,
+            concurrency,
+            channels: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub async fn enqueue(
+        &self,
+        ruleset: Ruleset,
+        deck_ids: Vec<String>,
+    ) -> Result<job::Model, AppError> {
+        let job = self
+            .store
+            .create_job(ruleset.name().to_string(), deck_ids)
+            .await?;
+        self.spawn(job.clone());
+        Ok(job)
+    }
+
+    pub async fn resume(&self) -> Result<usize, AppError> {
+        let jobs = self.store.get_unfinished().await?;
+        for job in &jobs {
+            self.store.reset_running(job.id).await?;
+            self.spawn(job.clone());
+        }
+        Ok(jobs.len())
+    }
+
+    pub fn subscribe(&self, job_id: i32) -> Option<broadcast::Receiver<ProgressMessage>> {
+        self.channels
+            .lock()
+            .unwrap()
+            .get(&job_id)
+            .map(|sender| sender.subscribe())
+    }
+
+    pub async fn status(&self, job_id: i32) -> Result<JobStatus, AppError> {
+        let job = self.store.get_job(job_id).await?;
+        let decks = self.store.get_decks(job_id).await?;
+        let count = |status: JobDeckStatus| decks.iter().filter(|d| d.status == status).count();
+
+        Ok(JobStatus {
+            id: job.id,
+            ruleset: job.ruleset,
+            created_at: job.created_at,
+            finished_at: job.finished_at,
+            error: job.error,
+            total: decks.len(),
+            pending: count(JobDeckStatus::Pending),
+            running: count(JobDeckStatus::Running),
+            done: count(JobDeckStatus::Done),
+            failed: count(JobDeckStatus::Failed),
+            inconclusive: decks
+                .iter()
+                .filter(|d| d.status == JobDeckStatus::Done && d.is_valid.is_none())
+                .count(),
+            decks,
+        })
+    }
+
+    fn spawn(&self, job: job::Model) {
+        let (sender, _) = broadcast::channel(PROGRESS_CHANNEL_CAPACITY);
+        self.channels.lock().unwrap().insert(job.id, sender.clone());
+
+        let runner = self.clone();
+        tokio::spawn(async move {
+            let job_id = job.id;
+            // A job that cannot be finalised stays unfinished and is resumed on the next start.
+            let _ = runner.run(job, &sender).await;
+            runner.channels.lock().unwrap().remove(&job_id);
+        });
+    }
+
+    async fn run(
+        &self,
+        job: job::Model,
+        sender: &broadcast::Sender<ProgressMessage>,
+    ) -> Result<(), AppError> {
+        let error = match self.run_decks(&job, sender).await {
+            Ok(errors) if errors.is_empty() => None,
+            Ok(errors) => Some(errors.join("; ")),
+            Err(e) => Some(e.to_string()),
+        };
+        self.store.finish_job(job, error).await?;
+
+        Ok(())
+    }
+
+    async fn run_decks(
+        &self,
+        job: &job::Model,
+        sender: &broadcast::Sender<ProgressMessage>,
+    ) -> Result<Vec<String>, AppError> {
+        let ruleset = Ruleset::from_name(&job.ruleset).unwrap_or_default();
+        let decks = self.store.get_decks(job.id).await?;
+        let total = decks.len();
+        let completed = AtomicUsize::new(
+            decks
+                .iter()
+                .filter(|d| matches!(d.status, JobDeckStatus::Done | JobDeckStatus::Failed))
+                .count(),
+        );
+
+        let pending: Vec<_> = decks
+            .into_iter()
+            .filter(|d| d.status == JobDeckStatus::Pending)
+            .collect();
+
+        let results = stream::iter(pending)
+            .map(|deck| self.run_deck(deck, &ruleset, sender, &completed, total))
+            .buffer_unordered(self.concurrency)
+            .collect::<Vec<_>>()
+            .await;
+
+        Ok(results
+            .into_iter()
+            .filter_map(Result::err)
+            .map(|e| e.to_string())
+            .collect())
+    }
+
+    async fn run_deck(
+        &self,
+        deck: job_deck::Model,
+        ruleset: &Ruleset,
+        sender: &broadcast::Sender<ProgressMessage>,
+        completed: &AtomicUsize,
+        total: usize,
+    ) -> Result<(), AppError> {
+        let deck_id = deck.deck_id.clone();
+        let deck = self
+            .store
+            .update_deck(deck, JobDeckStatus::Running, None, None)
+            .await?;
+
+        let tracker =
+            ProgressTracker::for_deck(sender.clone(), deck_id.clone(), ruleset.validators().len());
+        tracker.send(ProgressEvent::Started { ruleset: *ruleset });
+        let result = async {
+            let list = fetch_list(&self.upstream, &deck_id).await?;
+            if let Some(report) = self.history.find_recent(&list, ruleset).await? {
+                return Ok((report, true));
+            }
+            let report = list
+                .validate_with_progress(&self.upstream, ruleset, Some(&tracker))
+                .await?;
+            Ok::<_, AppError>((report, false))
+        }
+        .await;
+
+        match result {
+            Ok((report, cached)) => {
+                if !cached {
+                    self.history.save(report.clone()).await?;
+                }
+                self.store
+                    .update_deck(deck, JobDeckStatus::Done, Some(&report), None)
+                    .await?;
+                tracker.complete(report);
+            }
+            Err(e) => {
+                let body = e.to_body(None);
+                let error = serde_json::to_value(&body).unwrap();
+                self.store
+                    .update_deck(deck, JobDeckStatus::Failed, None, Some(error))
+                    .await?;
+                tracker.fail(body);
+            }
+        }
+        let _ = sender.send(ProgressMessage::new(
+            None,
+            ProgressEvent::JobProgress {
+                completed: completed.fetch_add(1, Ordering::SeqCst) + 1,
+                total,
+            },
+        ));
+
+        Ok(())
+    }
+}
+
+#[post("/jobs", data = "<new_job>")]
+pub async fn create_job(
+    new_job: Json<NewJob>,
+    runner: &State<JobRunner>,
+    config: &State<AppConfig>,
+) -> Result<Accepted<Json<JobStatus>>, AppError> {
+    let new_job = new_job.into_inner();
+    let ruleset = match new_job.ruleset {
+        Some(name) => Ruleset::from_name(&name)
+            .ok_or_else(|| AppError::InvalidRequest(format!("Unknown ruleset {}", name)))?,
+        None => config.default_ruleset,
+    };
+
+    let job = runner.enqueue(ruleset, new_job.ids).await?;
+    Ok(Accepted(Json(runner.status(job.id).await?)))
+}
+
+#[get("/jobs/<job_id>")]
+pub async fn get_job(job_id: i32, runner: &State<JobRunner>) -> Result<Json<JobStatus>, AppError> {
+    Ok(Json(runner.status(job_id).await?))
+}
+
+#[get("/ws/jobs/<job_id>")]
+pub fn job_ws(
+    job_id: i32,
+    ws: WebSocket,
+    runner: &State<JobRunner>,
+    request_id: &RequestId,
+) -> Channel<'static> {
+    let runner = runner.inner().clone();
+    let request_id = request_id.0.clone();
+
+    ws.channel(move |mut stream| {
+        Box::pin(async move {
+            if let Some(mut rx) = runner.subscribe(job_id) {
+                loop {
+                    match rx.recv().await {
+                        Ok(progress_msg) => {
+                            let _ = stream.send(Message::Text(progress_msg.to_json())).await;
+                        }
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            }
+
+            let event = match runner.status(job_id).await {
+                Ok(status) => ProgressEvent::JobFinished(status),
+                Err(e) => ProgressEvent::Error(e.to_body(Some(request_id.clone()))),
+            };
+            let frame = ProgressMessage::new(None, event).to_json();
+            let _ = stream.send(Message::Text(frame)).await;
+
+            Ok(())
+        })
+    })
+}
@@ -0,0 +1,51 @@
+#[cfg(feature = "server")]
+#[macro_use]
+extern crate rocket;
+
+pub mod cache;
+pub mod classify;
+pub mod cli;
+pub mod config;
+pub mod decklist;
+pub mod errors;
+pub mod http;
+pub mod models;
+pub mod moxfield;
+pub mod progress;
+pub mod ruleset;
+pub mod scryfall;
+pub mod single_flight;
+pub mod upstream;
+pub mod validation_results;
+pub mod validators;
+
+#[cfg(feature = "server")]
+pub mod admin;
+#[cfg(feature = "server")]
+pub mod authors;
+#[cfg(feature = "server")]
+pub mod entities;
+#[cfg(feature = "server")]
+pub mod events;
+#[cfg(feature = "server")]
+pub mod inflight;
+#[cfg(feature = "server")]
+pub mod jobs;
+#[cfg(feature = "server")]
+pub mod migrator;
+#[cfg(feature = "server")]
+pub mod persistence;
+#[cfg(feature = "server")]
+pub mod request_id;
+#[cfg(feature = "server")]
+pub mod responder;
+#[cfg(feature = "server")]
+pub mod routes;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "server")]
+pub mod sse;
+#[cfg(feature = "server")]
+pub mod stats;
+#[cfg(feature = "server")]
+pub mod ws;
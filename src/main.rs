@@ -1,43 +1,28 @@
-mod entities;
-mod errors;
-mod migrator;
-mod models;
-mod persistence;
-mod progress;
-mod routes;
-mod validation_results;
-mod validators;
-mod ws;
+use moxfield_list_verifyer::{cli, server};
 
-#[macro_use]
-extern crate rocket;
-
-use migrator::Migrator;
-use persistence::HistoryStore;
-use sea_orm::Database;
-use sea_orm_migration::MigratorTrait;
-
-#[launch]
-async fn rocket() -> _ {
+#[rocket::main]
+async fn main() {
     dotenvy::dotenv().ok();
-    let client = reqwest::Client::new();
-
-    let db_url = "sqlite://data/sqlite.db?mode=rwc";
-    let conn = Database::connect(db_url)
-        .await
-        .expect("Failed to connect to database");
 
-    Migrator::up(&conn, None).await.expect("Migration failed");
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == "check") {
+        let config = match server::load_cli_config() {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(cli::EXIT_ERROR);
+            }
+        };
+        std::process::exit(cli::run(&args[1..], &config).await);
+    }
 
-    let history_store = HistoryStore::new(conn);
+    let config = match server::load_config() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(cli::EXIT_ERROR);
+        }
+    };
 
-    rocket::build().manage(client).manage(history_store).mount(
-        "/",
-        routes![
-            routes::validate,
-            routes::validate_batch,
-            routes::get_history,
-            ws::validate_ws
-        ],
-    )
+    let _ = server::rocket(config).await.launch().await;
 }
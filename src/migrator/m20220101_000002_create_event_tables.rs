@@ -0,0 +1,104 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Event::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Event::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Event::Name).string().not_null())
+                    .col(ColumnDef::new(Event::Ruleset).string().not_null())
+                    .col(ColumnDef::new(Event::LockedAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(Event::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(EventPlayer::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(EventPlayer::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(EventPlayer::EventId).integer().not_null())
+                    .col(ColumnDef::new(EventPlayer::PlayerName).string().not_null())
+                    .col(ColumnDef::new(EventPlayer::DeckId).string().not_null())
+                    .col(ColumnDef::new(EventPlayer::LockedDeckList).json())
+                    .col(ColumnDef::new(EventPlayer::IsValid).boolean())
+                    .col(ColumnDef::new(EventPlayer::Report).json())
+                    .col(ColumnDef::new(EventPlayer::Error).string())
+                    .col(
+                        ColumnDef::new(EventPlayer::ChangedAfterLock)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(EventPlayer::CheckedAt).timestamp_with_time_zone())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_event_player_event")
+                            .from(EventPlayer::Table, EventPlayer::EventId)
+                            .to(Event::Table, Event::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(EventPlayer::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Event::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+    Id,
+    Name,
+    Ruleset,
+    LockedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum EventPlayer {
+    Table,
+    Id,
+    EventId,
+    PlayerName,
+    DeckId,
+    LockedDeckList,
+    IsValid,
+    Report,
+    Error,
+    ChangedAfterLock,
+    CheckedAt,
+}
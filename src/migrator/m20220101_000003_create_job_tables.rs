@@ -0,0 +1,97 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Job::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Job::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Job::Ruleset).string().not_null())
+                    .col(
+                        ColumnDef::new(Job::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Job::FinishedAt).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(JobDeck::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(JobDeck::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(JobDeck::JobId).integer().not_null())
+                    .col(ColumnDef::new(JobDeck::DeckId).string().not_null())
+                    .col(ColumnDef::new(JobDeck::Status).string_len(16).not_null())
+                    .col(ColumnDef::new(JobDeck::IsValid).boolean())
+                    .col(ColumnDef::new(JobDeck::Report).json())
+                    .col(ColumnDef::new(JobDeck::Error).json())
+                    .col(
+                        ColumnDef::new(JobDeck::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_job_deck_job")
+                            .from(JobDeck::Table, JobDeck::JobId)
+                            .to(Job::Table, Job::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(JobDeck::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Job::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Job {
+    Table,
+    Id,
+    Ruleset,
+    CreatedAt,
+    FinishedAt,
+}
+
+#[derive(DeriveIden)]
+enum JobDeck {
+    Table,
+    Id,
+    JobId,
+    DeckId,
+    Status,
+    IsValid,
+    Report,
+    Error,
+    UpdatedAt,
+}
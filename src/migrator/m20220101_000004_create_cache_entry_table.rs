@@ -0,0 +1,48 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CacheEntry::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(CacheEntry::Source).string().not_null())
+                    .col(ColumnDef::new(CacheEntry::Key).text().not_null())
+                    .col(ColumnDef::new(CacheEntry::Value).json().not_null())
+                    .col(
+                        ColumnDef::new(CacheEntry::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(CacheEntry::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .primary_key(Index::create().col(CacheEntry::Source).col(CacheEntry::Key))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CacheEntry::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CacheEntry {
+    Table,
+    Source,
+    Key,
+    Value,
+    CreatedAt,
+    ExpiresAt,
+}
@@ -0,0 +1,58 @@
+use sea_orm::DatabaseBackend;
+use sea_orm_migration::prelude::*;
+
+const JSON_COLUMNS: [(&str, &str); 14] = [
+    ("report", "non_land_tutors"),
+    ("report", "mass_land_denial_cards"),
+    ("report", "commander_tutors"),
+    ("report", "two_card_combos"),
+    ("report", "gamechangers"),
+    ("report", "infinite_turns_combos"),
+    ("report", "combos"),
+    ("report", "deck_list"),
+    ("report", "incomplete_lookups"),
+    ("event_player", "locked_deck_list"),
+    ("event_player", "report"),
+    ("job_deck", "report"),
+    ("job_deck", "error"),
+    ("cache_entry", "value"),
+];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+async fn set_json_type(manager: &SchemaManager<'_>, binary: bool) -> Result<(), DbErr> {
+    if manager.get_database_backend() != DatabaseBackend::Postgres {
+        return Ok(());
+    }
+
+    for (table, column) in JSON_COLUMNS {
+        let mut column = ColumnDef::new(Alias::new(column));
+        if binary {
+            column.json_binary();
+        } else {
+            column.json();
+        }
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new(table))
+                    .modify_column(&mut column)
+                    .to_owned(),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        set_json_type(manager, true).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        set_json_type(manager, false).await
+    }
+}
@@ -0,0 +1,134 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReportFinding::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ReportFinding::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ReportFinding::ReportId).integer().not_null())
+                    .col(
+                        ColumnDef::new(ReportFinding::FindingIndex)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ReportFinding::Category).string().not_null())
+                    .col(ColumnDef::new(ReportFinding::Card).string().not_null())
+                    .col(ColumnDef::new(ReportFinding::Detail).text())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_report_finding_report")
+                            .from(ReportFinding::Table, ReportFinding::ReportId)
+                            .to(Report::Table, Report::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReportCard::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ReportCard::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ReportCard::ReportId).integer().not_null())
+                    .col(ColumnDef::new(ReportCard::Card).string().not_null())
+                    .col(ColumnDef::new(ReportCard::Quantity).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_report_card_report")
+                            .from(ReportCard::Table, ReportCard::ReportId)
+                            .to(Report::Table, Report::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        for index in [
+            Index::create()
+                .name("idx_report_finding_report_id")
+                .table(ReportFinding::Table)
+                .col(ReportFinding::ReportId)
+                .to_owned(),
+            Index::create()
+                .name("idx_report_finding_card")
+                .table(ReportFinding::Table)
+                .col(ReportFinding::Card)
+                .to_owned(),
+            Index::create()
+                .name("idx_report_finding_category")
+                .table(ReportFinding::Table)
+                .col(ReportFinding::Category)
+                .to_owned(),
+            Index::create()
+                .name("idx_report_card_report_id")
+                .table(ReportCard::Table)
+                .col(ReportCard::ReportId)
+                .to_owned(),
+            Index::create()
+                .name("idx_report_card_card")
+                .table(ReportCard::Table)
+                .col(ReportCard::Card)
+                .to_owned(),
+        ] {
+            manager.create_index(index).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ReportCard::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(ReportFinding::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Report {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum ReportFinding {
+    Table,
+    Id,
+    ReportId,
+    FindingIndex,
+    Category,
+    Card,
+    Detail,
+}
+
+#[derive(DeriveIden)]
+enum ReportCard {
+    Table,
+    Id,
+    ReportId,
+    Card,
+    Quantity,
+}
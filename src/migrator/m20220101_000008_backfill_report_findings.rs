@@ -0,0 +1,178 @@
+use sea_orm::{ConnectionTrait, QueryResult};
+use sea_orm_migration::prelude::*;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+// Row shapes as of this migration, kept local so later model changes cannot alter the backfill.
+#[derive(Deserialize)]
+struct DeckListRow {
+    card: String,
+    quantity: u32,
+}
+
+struct BackfillFinding {
+    category: &'static str,
+    cards: Vec<String>,
+    detail: Option<String>,
+}
+
+fn json_column<T: DeserializeOwned + Default>(row: &QueryResult, column: &str) -> T {
+    row.try_get::<serde_json::Value>("", column)
+        .ok()
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+fn findings(row: &QueryResult) -> Vec<BackfillFinding> {
+    let mass_land_denial: Vec<(String, String)> = json_column(row, "mass_land_denial_cards");
+    let non_land_tutors: Vec<(String, String)> = json_column(row, "non_land_tutors");
+    let commander_tutors: Vec<String> = json_column(row, "commander_tutors");
+    let two_card_combos: Vec<(Vec<String>, String)> = json_column(row, "two_card_combos");
+    let gamechangers: Vec<String> = json_column(row, "gamechangers");
+    let infinite_turns: Vec<Vec<String>> = json_column(row, "infinite_turns_combos");
+
+    let finding = |category, cards, detail| BackfillFinding {
+        category,
+        cards,
+        detail,
+    };
+
+    let mut findings = Vec::new();
+    for (card, oracle_text) in mass_land_denial {
+        findings.push(finding("mass_land_denial", vec![card], Some(oracle_text)));
+    }
+    for (card, oracle_text) in non_land_tutors {
+        findings.push(finding("non_land_tutor", vec![card], Some(oracle_text)));
+    }
+    for card in commander_tutors {
+        findings.push(finding("commander_tutor", vec![card], None));
+    }
+    for (cards, description) in two_card_combos {
+        findings.push(finding("two_card_combo", cards, Some(description)));
+    }
+    for card in gamechangers {
+        findings.push(finding("gamechanger", vec![card], None));
+    }
+    for cards in infinite_turns {
+        findings.push(finding("infinite_turns", cards, None));
+    }
+    findings
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let backend = manager.get_database_backend();
+
+        let select = Query::select()
+            .columns([
+                Report::Id,
+                Report::MassLandDenialCards,
+                Report::NonLandTutors,
+                Report::CommanderTutors,
+                Report::TwoCardCombos,
+                Report::Gamechangers,
+                Report::InfiniteTurnsCombos,
+                Report::DeckList,
+            ])
+            .from(Report::Table)
+            .to_owned();
+        let rows = db.query_all(backend.build(&select)).await?;
+
+        for row in rows {
+            let report_id: i32 = row.try_get("", "id")?;
+            let deck_list: Vec<DeckListRow> = json_column(&row, "deck_list");
+
+            let mut inserts = Query::insert()
+                .into_table(ReportFinding::Table)
+                .columns([
+                    ReportFinding::ReportId,
+                    ReportFinding::FindingIndex,
+                    ReportFinding::Category,
+                    ReportFinding::Card,
+                    ReportFinding::Detail,
+                ])
+                .to_owned();
+            let mut has_findings = false;
+            for (index, finding) in findings(&row).into_iter().enumerate() {
+                for card in finding.cards {
+                    inserts.values_panic([
+                        report_id.into(),
+                        (index as i32).into(),
+                        finding.category.into(),
+                        card.into(),
+                        finding.detail.clone().into(),
+                    ]);
+                    has_findings = true;
+                }
+            }
+            if has_findings {
+                db.execute(backend.build(&inserts)).await?;
+            }
+
+            if !deck_list.is_empty() {
+                let mut cards = Query::insert()
+                    .into_table(ReportCard::Table)
+                    .columns([ReportCard::ReportId, ReportCard::Card, ReportCard::Quantity])
+                    .to_owned();
+                for unit in deck_list {
+                    cards.values_panic([
+                        report_id.into(),
+                        unit.card.into(),
+                        (unit.quantity as i32).into(),
+                    ]);
+                }
+                db.execute(backend.build(&cards)).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let backend = manager.get_database_backend();
+
+        db.execute(backend.build(Query::delete().from_table(ReportCard::Table)))
+            .await?;
+        db.execute(backend.build(Query::delete().from_table(ReportFinding::Table)))
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Report {
+    Table,
+    Id,
+    MassLandDenialCards,
+    NonLandTutors,
+    CommanderTutors,
+    TwoCardCombos,
+    Gamechangers,
+    InfiniteTurnsCombos,
+    DeckList,
+}
+
+#[derive(DeriveIden)]
+enum ReportFinding {
+    Table,
+    ReportId,
+    FindingIndex,
+    Category,
+    Card,
+    Detail,
+}
+
+#[derive(DeriveIden)]
+enum ReportCard {
+    Table,
+    ReportId,
+    Card,
+    Quantity,
+}
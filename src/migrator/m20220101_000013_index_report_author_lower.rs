@@ -0,0 +1,51 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_report_author")
+                    .table(Report::Table)
+                    .to_owned(),
+            )
+            .await?;
+        // Author lookups compare lower(author), which a plain column index cannot serve.
+        manager
+            .get_connection()
+            .execute_unprepared("CREATE INDEX idx_report_author_lower ON report (lower(author))")
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_report_author_lower")
+                    .table(Report::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_report_author")
+                    .table(Report::Table)
+                    .col(Report::Author)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Report {
+    Table,
+    Author,
+}
@@ -0,0 +1,70 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_report_card_card")
+                    .table(ReportCard::Table)
+                    .to_owned(),
+            )
+            .await?;
+        // Card filters compare lower(card); report_finding keeps its plain index for the
+        // per-card stats, which group by the stored spelling.
+        let conn = manager.get_connection();
+        conn.execute_unprepared(
+            "CREATE INDEX idx_report_card_card_lower ON report_card (lower(card))",
+        )
+        .await?;
+        conn.execute_unprepared(
+            "CREATE INDEX idx_report_finding_card_lower ON report_finding (lower(card))",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_report_finding_card_lower")
+                    .table(ReportFinding::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_report_card_card_lower")
+                    .table(ReportCard::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_report_card_card")
+                    .table(ReportCard::Table)
+                    .col(ReportCard::Card)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ReportCard {
+    Table,
+    Card,
+}
+
+#[derive(DeriveIden)]
+enum ReportFinding {
+    Table,
+}
@@ -1,12 +1,44 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_report_table;
+mod m20220101_000002_create_event_tables;
+mod m20220101_000003_create_job_tables;
+mod m20220101_000004_create_cache_entry_table;
+mod m20220101_000005_add_report_completeness;
+mod m20220101_000006_use_jsonb_on_postgres;
+mod m20220101_000007_create_report_finding_tables;
+mod m20220101_000008_backfill_report_findings;
+mod m20220101_000009_add_report_metadata;
+mod m20220101_000010_add_report_content_hash;
+mod m20220101_000011_add_job_error;
+mod m20220101_000012_add_report_unknown_cards;
+mod m20220101_000013_index_report_author_lower;
+mod m20220101_000014_add_report_deck_id;
+mod m20220101_000015_backfill_report_created_at;
+mod m20220101_000016_index_report_cards_lower;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20220101_000001_create_report_table::Migration)]
+        vec![
+            Box::new(m20220101_000001_create_report_table::Migration),
+            Box::new(m20220101_000002_create_event_tables::Migration),
+            Box::new(m20220101_000003_create_job_tables::Migration),
+            Box::new(m20220101_000004_create_cache_entry_table::Migration),
+            Box::new(m20220101_000005_add_report_completeness::Migration),
+            Box::new(m20220101_000006_use_jsonb_on_postgres::Migration),
+            Box::new(m20220101_000007_create_report_finding_tables::Migration),
+            Box::new(m20220101_000008_backfill_report_findings::Migration),
+            Box::new(m20220101_000009_add_report_metadata::Migration),
+            Box::new(m20220101_000010_add_report_content_hash::Migration),
+            Box::new(m20220101_000011_add_job_error::Migration),
+            Box::new(m20220101_000012_add_report_unknown_cards::Migration),
+            Box::new(m20220101_000013_index_report_author_lower::Migration),
+            Box::new(m20220101_000014_add_report_deck_id::Migration),
+            Box::new(m20220101_000015_backfill_report_created_at::Migration),
+            Box::new(m20220101_000016_index_report_cards_lower::Migration),
+        ]
     }
 }
@@ -1,6 +1,5 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::validators::Validator;
 use ::serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -16,6 +15,20 @@ pub struct Report {
     pub infinite_turns_combos: Vec<Vec<String>>,
     pub combos: Vec<(Vec<String>, String)>,
     pub deck_list: Vec<CardListUnit>,
+    #[serde(default)]
+    pub inconclusive: bool,
+    #[serde(default)]
+    pub incomplete_lookups: Vec<String>,
+    #[serde(default)]
+    pub unknown_cards: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ruleset: Option<Ruleset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deck_id: Option<String>,
 }
 
 impl Report {
@@ -32,13 +45,35 @@ impl Report {
             infinite_turns_combos: Vec::new(),
             combos: Vec::new(),
             deck_list,
+            inconclusive: false,
+            incomplete_lookups: Vec::new(),
+            unknown_cards: Vec::new(),
+            ruleset: None,
+            created_at: None,
+            content_hash: None,
+            deck_id: None,
         }
     }
+
+    pub fn findings(&self) -> Vec<Finding> {
+        ValidationResults {
+            mass_land_denial_cards: self.mass_land_denial_cards.clone(),
+            non_land_tutors: self.non_land_tutors.clone(),
+            commander_tutors: self.commander_tutors.clone(),
+            two_card_combos: self.two_card_combos.clone(),
+            gamechangers: self.gamechangers.clone(),
+            infinite_turns_combos: self.infinite_turns_combos.clone(),
+            ..ValidationResults::default()
+        }
+        .findings()
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct List {
     pub id: String,
+    #[serde(rename = "publicId", default)]
+    pub public_id: Option<String>,
     pub name: String,
     pub format: String,
     pub visibility: String,
@@ -83,7 +118,7 @@ pub struct CardList {
     pub main: Vec<CardListUnit>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct CardListUnit {
     pub card: String,
     pub quantity: u32,
@@ -135,32 +170,170 @@ pub struct ScryfallQuery {
     pub data: Vec<ScryfallCard>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScryfallCollectionRequest {
+    pub identifiers: Vec<ScryfallIdentifier>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScryfallIdentifier {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScryfallCollection {
+    pub data: Vec<ScryfallCard>,
+    #[serde(default)]
+    pub not_found: Vec<ScryfallIdentifier>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ScryfallCard {
     pub name: String,
-    pub oracle_text: String,
+    #[serde(default)]
+    pub oracle_text: Option<String>,
+    #[serde(default)]
+    pub type_line: String,
+    #[serde(default)]
+    pub card_faces: Option<Vec<ScryfallCardFace>>,
+    #[serde(default)]
+    pub color_identity: Vec<String>,
+    #[serde(default)]
+    pub legalities: HashMap<String, String>,
+    #[serde(default)]
     pub game_changer: bool,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScryfallCardFace {
+    pub name: String,
+    #[serde(default)]
+    pub oracle_text: Option<String>,
+    #[serde(default)]
+    pub type_line: Option<String>,
+}
+
+impl ScryfallCard {
+    pub fn oracle_texts(&self) -> Vec<&str> {
+        let mut texts: Vec<&str> = self.oracle_text.iter().map(String::as_str).collect();
+        if let Some(faces) = &self.card_faces {
+            texts.extend(faces.iter().filter_map(|face| face.oracle_text.as_deref()));
+        }
+        texts
+    }
+
+    pub fn full_oracle_text(&self) -> String {
+        self.oracle_texts().join("\n//\n")
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DeckCards {
+    cards: HashMap<String, ScryfallCard>,
+    not_found: HashSet<String>,
+    failed: HashMap<String, String>,
+}
+
+impl DeckCards {
+    pub fn new(
+        cards: HashMap<String, ScryfallCard>,
+        not_found: HashSet<String>,
+        failed: HashMap<String, String>,
+    ) -> Self {
+        Self {
+            cards,
+            not_found,
+            failed,
+        }
+    }
+
+    pub fn lookup(&self, name: &str, results: &mut ValidationResults) -> Option<&ScryfallCard> {
+        let card = self.cards.get(name);
+        if card.is_none() {
+            if self.not_found.contains(name) {
+                results.unknown_cards.push(name.to_string());
+            } else if let Some(error) = self.failed.get(name) {
+                results
+                    .incomplete_lookups
+                    .push(format!("Scryfall: lookup failed for {}: {}", name, error));
+            } else {
+                results
+                    .incomplete_lookups
+                    .push(format!("Scryfall: no card data for {}", name));
+            }
+        }
+        card
+    }
+}
+
 use crate::errors::AppError;
-use crate::progress::ProgressTracker;
-use crate::validation_results::ValidationResults;
-use std::sync::{Arc, Mutex};
+use crate::progress::{ProgressEvent, ProgressTracker};
+use crate::ruleset::Ruleset;
+use crate::upstream::Upstream;
+use crate::validation_results::{FailMode, Finding, ValidationResults};
+use sha2::{Digest, Sha256};
 
 impl List {
+    pub fn normalized_cards(&self) -> Vec<CardListUnit> {
+        let mut cards: Vec<CardListUnit> = self
+            .boards
+            .commanders
+            .cards
+            .values()
+            .chain(self.boards.mainboard.cards.values())
+            .map(|c| CardListUnit {
+                card: c.card.name.clone(),
+                quantity: c.quantity,
+            })
+            .collect();
+        cards.sort_by(|a, b| a.card.cmp(&b.card));
+        cards
+    }
+
+    pub fn content_hash(&self, ruleset: &Ruleset) -> String {
+        let mut hasher = Sha256::new();
+        for (section, board) in [
+            ("commanders", &self.boards.commanders),
+            ("mainboard", &self.boards.mainboard),
+        ] {
+            let mut cards: Vec<(String, u32)> = board
+                .cards
+                .values()
+                .map(|c| (c.card.name.trim().to_lowercase(), c.quantity))
+                .collect();
+            cards.sort();
+
+            hasher.update(format!("{}\n", section));
+            for (name, quantity) in cards {
+                hasher.update(format!("{} {}\n", quantity, name));
+            }
+        }
+        hasher.update(format!(
+            "ruleset {} v{}\n",
+            ruleset.name(),
+            Ruleset::VERSION
+        ));
+
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
     pub async fn validate(
         &self,
-        client: &reqwest::Client,
-        validators: Vec<Box<dyn Validator>>,
+        upstream: &Upstream,
+        ruleset: &Ruleset,
     ) -> Result<Report, AppError> {
-        self.validate_with_progress(client, validators, None).await
+        self.validate_with_progress(upstream, ruleset, None).await
     }
 
     pub async fn validate_with_progress(
         &self,
-        client: &reqwest::Client,
-        validators: Vec<Box<dyn Validator>>,
-        progress_tracker: Option<Arc<Mutex<ProgressTracker>>>,
+        upstream: &Upstream,
+        ruleset: &Ruleset,
+        progress: Option<&ProgressTracker>,
     ) -> Result<Report, AppError> {
         let deck_list: Vec<CardListUnit> = self
             .boards
@@ -173,27 +346,36 @@ impl List {
             })
             .collect();
 
-        println!(
+        eprintln!(
             "Validating list {} by {}",
             self.name, self.created_by_user.user_name
         );
+        if let Some(progress) = progress {
+            progress.send(ProgressEvent::DeckFetched {
+                name: self.name.clone(),
+                author: self.created_by_user.user_name.clone(),
+                cards: self.boards.mainboard.cards.len() + self.boards.commanders.cards.len(),
+            });
+        }
 
-        let validation_futures: Vec<_> = validators
+        let cards = upstream.resolve_cards(self).await;
+
+        let validation_futures: Vec<_> = ruleset
+            .validators()
             .into_iter()
             .map(|validator| {
-                let validator_name = validator.name().to_string();
-                let progress_clone = progress_tracker.clone();
+                let cards = &cards;
 
                 async move {
-                    let result = validator.check(client, self).await;
-
-                    if let Some(tracker) = progress_clone {
-                        if let Ok(mut tracker_guard) = tracker.lock() {
-                            tracker_guard.update(
-                                validator_name.clone(),
-                                format!("Completed {}", validator_name),
-                            );
+                    let result = validator.check(upstream, self, cards, progress).await;
+
+                    if let Some(progress) = progress
+                        && let Ok(results) = &result
+                    {
+                        for finding in results.findings() {
+                            progress.send(ProgressEvent::Finding(finding));
                         }
+                        progress.validator_done(validator.name());
                     }
 
                     result
@@ -209,7 +391,13 @@ impl List {
             aggregated_results = aggregated_results.merge(validation_result);
         }
 
-        let is_valid = aggregated_results.is_valid();
+        let passes_rules = aggregated_results.is_valid(ruleset);
+        let mut incomplete_lookups = aggregated_results.incomplete_lookups;
+        incomplete_lookups.sort();
+        incomplete_lookups.dedup();
+        let inconclusive = passes_rules
+            && !incomplete_lookups.is_empty()
+            && upstream.config.fail_mode == FailMode::Closed;
 
         let mut report = Report::new(
             self.name.clone(),
@@ -224,7 +412,16 @@ impl List {
         report.gamechangers = aggregated_results.gamechangers;
         report.infinite_turns_combos = aggregated_results.infinite_turns_combos;
         report.combos = aggregated_results.combos;
-        report.is_valid = is_valid;
+        report.is_valid = passes_rules && !inconclusive;
+        report.inconclusive = inconclusive;
+        report.incomplete_lookups = incomplete_lookups;
+        report.unknown_cards = aggregated_results.unknown_cards;
+        report.unknown_cards.sort();
+        report.unknown_cards.dedup();
+        report.ruleset = Some(*ruleset);
+        report.created_at = Some(chrono::Utc::now());
+        report.content_hash = Some(self.content_hash(ruleset));
+        report.deck_id = self.public_id.clone();
 
         Ok(report)
     }
@@ -283,7 +480,67 @@ impl ComboList {
                 included.description.clone(),
             ));
         }
-        println!("Combos found: {}", combos.len());
+        eprintln!("Combos found: {}", combos.len());
         combos
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separates_unknown_cards_from_failed_lookups() {
+        let cards = DeckCards::new(
+            HashMap::new(),
+            HashSet::from(["Sol Rnig".to_string()]),
+            HashMap::from([("Forest".to_string(), "503 Service Unavailable".to_string())]),
+        );
+        let mut results = ValidationResults::default();
+
+        assert!(cards.lookup("Sol Rnig", &mut results).is_none());
+        assert!(cards.lookup("Forest", &mut results).is_none());
+        assert!(cards.lookup("Island", &mut results).is_none());
+
+        assert_eq!(results.unknown_cards, ["Sol Rnig"]);
+        assert_eq!(
+            results.incomplete_lookups,
+            [
+                "Scryfall: lookup failed for Forest: 503 Service Unavailable",
+                "Scryfall: no card data for Island"
+            ]
+        );
+    }
+
+    #[test]
+    fn content_hash_ignores_card_order_and_case() {
+        let hash = |text: &str| {
+            crate::decklist::parse_decklist("deck", text)
+                .unwrap()
+                .content_hash(&Ruleset::House)
+        };
+        let deck = hash("Commander\n1 Atraxa, Praetors' Voice\nDeck\n1 Sol Ring\n2 Island\n");
+
+        assert_eq!(
+            deck,
+            hash("Commander\n1 atraxa, praetors' voice\nDeck\n2 ISLAND\n1 Sol Ring\n")
+        );
+        assert_ne!(
+            deck,
+            hash("Commander\n1 Atraxa, Praetors' Voice\nDeck\n1 Sol Ring\n3 Island\n")
+        );
+        assert_ne!(
+            deck,
+            hash("Commander\n1 Sol Ring\nDeck\n1 Atraxa, Praetors' Voice\n2 Island\n")
+        );
+        assert_ne!(
+            deck,
+            crate::decklist::parse_decklist(
+                "deck",
+                "Commander\n1 Atraxa, Praetors' Voice\nDeck\n1 Sol Ring\n2 Island\n"
+            )
+            .unwrap()
+            .content_hash(&Ruleset::Bracket2)
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use crate::errors::AppError;
+use crate::http::retry_after;
+use crate::models::List;
+use crate::upstream::Upstream;
+use reqwest::{Response, StatusCode};
+use serde::Deserialize;
+
+const USER_DECKS_PAGE_SIZE: u32 = 100;
+const MAX_USER_DECK_PAGES: u32 = 20;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct DeckSummary {
+    #[serde(rename = "publicId")]
+    pub public_id: String,
+    pub name: String,
+    pub format: String,
+    #[serde(default)]
+    pub visibility: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct DeckPage {
+    #[serde(rename = "totalPages", default)]
+    total_pages: u32,
+    #[serde(default)]
+    data: Vec<DeckSummary>,
+}
+
+async fn send(upstream: &Upstream, url: String) -> Result<Response, AppError> {
+    let config = &upstream.config;
+    if config.moxfield_user_agent.is_empty() {
+        return Err(AppError::Configuration(
+            "moxfield_user_agent is required (set MOXFIELD_USER_AGENT)".to_string(),
+        ));
+    }
+    let request = upstream
+        .http
+        .get(url)
+        .header("User-Agent", &config.moxfield_user_agent)
+        .header("Accept", "application/json");
+    let response = upstream.http.send(request).await?;
+
+    if response.status() == StatusCode::TOO_MANY_REQUESTS {
+        return Err(AppError::MoxfieldRateLimited {
+            retry_after: retry_after(&response).map(|delay| delay.as_secs()),
+        });
+    }
+
+    Ok(response)
+}
+
+pub async fn fetch_list(upstream: &Upstream, id: &str) -> Result<List, AppError> {
+    if parse_deck_id(id).as_deref() != Some(id) {
+        return Err(AppError::InvalidDeckId(id.to_string()));
+    }
+
+    let url = format!("{}/v3/decks/all/{}", upstream.config.moxfield_api, id);
+    let response = send(upstream, url).await?;
+
+    match response.status() {
+        StatusCode::NOT_FOUND | StatusCode::GONE => {
+            return Err(AppError::DeckNotFound(id.to_string()));
+        }
+        StatusCode::FORBIDDEN | StatusCode::UNAUTHORIZED => {
+            return Err(AppError::DeckPrivate(id.to_string()));
+        }
+        StatusCode::BAD_REQUEST => return Err(AppError::InvalidDeckId(id.to_string())),
+        _ => {}
+    }
+
+    let mut list: List = response.error_for_status()?.json().await?;
+    if list.visibility.eq_ignore_ascii_case("private") {
+        return Err(AppError::DeckPrivate(id.to_string()));
+    }
+    list.public_id = Some(id.to_string());
+
+    Ok(list)
+}
+
+pub async fn fetch_user_decks(
+    upstream: &Upstream,
+    user_name: &str,
+) -> Result<Vec<DeckSummary>, AppError> {
+    if !is_user_name(user_name) {
+        return Err(AppError::InvalidRequest(format!(
+            "Invalid Moxfield user name {}",
+            user_name
+        )));
+    }
+
+    let mut decks = Vec::new();
+    let mut page = 1;
+    loop {
+        let url = format!(
+            "{}/v2/users/{}/decks?pageNumber={}&pageSize={}",
+            upstream.config.moxfield_api, user_name, page, USER_DECKS_PAGE_SIZE
+        );
+        let response = send(upstream, url).await?;
+        if matches!(response.status(), StatusCode::NOT_FOUND | StatusCode::GONE) {
+            return Err(AppError::AuthorNotFound(user_name.to_string()));
+        }
+
+        let deck_page: DeckPage = response.error_for_status()?.json().await?;
+        decks.extend(deck_page.data);
+        if page >= deck_page.total_pages || page >= MAX_USER_DECK_PAGES {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(decks)
+}
+
+fn is_user_name(input: &str) -> bool {
+    !input.is_empty()
+        && input
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.')
+}
+
+pub fn parse_deck_id(input: &str) -> Option<String> {
+    let input = input.trim().trim_end_matches('/');
+    let id = match input.find("moxfield.com/decks/") {
+        Some(index) => &input[index + "moxfield.com/decks/".len()..],
+        None => input,
+    };
+    let id = id.split(['/', '?', '#']).next().unwrap_or_default();
+
+    if !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        Some(id.to_string())
+    } else {
+        None
+    }
+}
@@ -1,20 +1,241 @@
-use crate::entities::{prelude::*, report};
+use crate::cache::{CacheBackend, CacheEntrySummary, CacheSource, CachedValue};
+use crate::entities::job_deck::JobDeckStatus;
+use crate::entities::{
+    cache_entry, event, event_player, job, job_deck, prelude::*, report, report_card,
+    report_finding,
+};
 use crate::errors::AppError;
-use crate::models::Report as ReportModel;
+use crate::models::{CardListUnit, List, Report as ReportModel};
+use crate::ruleset::Ruleset;
+use crate::validation_results::FindingCategory;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sea_orm::ActiveValue::Set;
-use sea_orm::{DatabaseConnection, EntityTrait};
+use sea_orm::sea_query::{
+    Alias, Condition, Expr, Func, IntoColumnRef, JoinType, LikeExpr, OnConflict, Order, Query,
+    SimpleExpr,
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseBackend, DatabaseConnection,
+    EntityTrait, FromQueryResult, IntoActiveModel, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, TransactionTrait,
+};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HistorySort {
+    #[default]
+    Newest,
+    Oldest,
+    Name,
+    Author,
+}
+
+impl HistorySort {
+    pub const ALL: [HistorySort; 4] = [
+        HistorySort::Newest,
+        HistorySort::Oldest,
+        HistorySort::Name,
+        HistorySort::Author,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HistorySort::Newest => "newest",
+            HistorySort::Oldest => "oldest",
+            HistorySort::Name => "name",
+            HistorySort::Author => "author",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|sort| sort.name().eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    pub author: Option<String>,
+    pub name: Option<String>,
+    pub is_valid: Option<bool>,
+    pub inconclusive: Option<bool>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub ruleset: Option<Ruleset>,
+    pub contains_card: Option<String>,
+    pub flagged_card: Option<String>,
+    pub category: Option<FindingCategory>,
+    pub sort: HistorySort,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+fn lower_eq(column: impl IntoColumnRef, value: &str) -> SimpleExpr {
+    Expr::expr(Func::lower(Expr::col(column))).eq(value.trim().to_lowercase())
+}
+
+fn like_pattern(value: &str) -> LikeExpr {
+    let escaped = value
+        .trim()
+        .to_lowercase()
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    LikeExpr::new(format!("%{}%", escaped)).escape('\\')
+}
+
+fn to_report_model(r: report::Model) -> ReportModel {
+    ReportModel {
+        is_valid: r.is_valid,
+        name: r.name,
+        author: r.author,
+        non_land_tutors: serde_json::from_value(r.non_land_tutors).unwrap_or_default(),
+        mass_land_denial_cards: serde_json::from_value(r.mass_land_denial_cards)
+            .unwrap_or_default(),
+        commander_tutors: serde_json::from_value(r.commander_tutors).unwrap_or_default(),
+        two_card_combos: serde_json::from_value(r.two_card_combos).unwrap_or_default(),
+        gamechangers: serde_json::from_value(r.gamechangers).unwrap_or_default(),
+        infinite_turns_combos: serde_json::from_value(r.infinite_turns_combos).unwrap_or_default(),
+        combos: serde_json::from_value(r.combos).unwrap_or_default(),
+        deck_list: serde_json::from_value(r.deck_list).unwrap_or_default(),
+        inconclusive: r.inconclusive,
+        incomplete_lookups: r
+            .incomplete_lookups
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default(),
+        ruleset: r.ruleset.as_deref().and_then(Ruleset::from_name),
+        created_at: r.created_at,
+        content_hash: r.content_hash,
+        deck_id: r.deck_id,
+        unknown_cards: r
+            .unknown_cards
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatsPeriod {
+    #[default]
+    Day,
+    Month,
+}
+
+impl StatsPeriod {
+    pub const ALL: [StatsPeriod; 2] = [StatsPeriod::Day, StatsPeriod::Month];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatsPeriod::Day => "day",
+            StatsPeriod::Month => "month",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|period| period.name().eq_ignore_ascii_case(name))
+    }
+
+    fn sqlite_format(&self) -> &'static str {
+        match self {
+            StatsPeriod::Day => "%Y-%m-%d",
+            StatsPeriod::Month => "%Y-%m",
+        }
+    }
+
+    fn postgres_format(&self) -> &'static str {
+        match self {
+            StatsPeriod::Day => "YYYY-MM-DD",
+            StatsPeriod::Month => "YYYY-MM",
+        }
+    }
+}
+
+#[derive(FromQueryResult, Debug)]
+pub struct PassRateRow {
+    pub period: String,
+    pub total: i64,
+    pub passed: i64,
+    pub inconclusive: i64,
+}
+
+#[derive(FromQueryResult, Debug)]
+pub struct TotalsRow {
+    pub total: i64,
+    pub passed: i64,
+    pub inconclusive: i64,
+    pub undated: i64,
+}
+
+#[derive(FromQueryResult, Debug)]
+pub struct FlaggedCardRow {
+    pub card: String,
+    pub decks: i64,
+    pub failed_decks: i64,
+}
+
+#[derive(FromQueryResult, Debug)]
+pub struct ComboRow {
+    pub first_card: String,
+    pub second_card: String,
+    pub decks: i64,
+}
+
+#[derive(FromQueryResult, Debug)]
+pub struct FindingCountRow {
+    pub category: String,
+    pub findings: i64,
+}
+
+#[derive(FromQueryResult, Debug)]
+pub struct AuthorStatsRow {
+    pub author: String,
+    pub reports: i64,
+    pub passed: i64,
+    pub inconclusive: i64,
+    pub decks: i64,
+    pub last_validated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(FromQueryResult, Debug)]
+pub struct AuthorDeckRow {
+    pub deck_id: Option<String>,
+    pub name: String,
+    pub author: String,
+    pub checks: i64,
+    pub passed: i64,
+    pub is_valid: bool,
+    pub inconclusive: bool,
+    pub ruleset: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
 
 #[derive(Clone)]
 pub struct HistoryStore {
     conn: DatabaseConnection,
+    report_max_age: Duration,
 }
 
 impl HistoryStore {
     pub fn new(conn: DatabaseConnection) -> Self {
-        Self { conn }
+        Self {
+            conn,
+            report_max_age: Duration::ZERO,
+        }
+    }
+
+    pub fn with_report_max_age(mut self, report_max_age: Duration) -> Self {
+        self.report_max_age = report_max_age;
+        self
     }
 
     pub async fn save(&self, report: ReportModel) -> Result<(), AppError> {
+        let findings = report.findings();
+        let deck_list = report.deck_list.clone();
+
         let active_model = report::ActiveModel {
             is_valid: Set(report.is_valid),
             name: Set(report.name),
@@ -29,11 +250,71 @@ impl HistoryStore {
             infinite_turns_combos: Set(serde_json::to_value(report.infinite_turns_combos).unwrap()),
             combos: Set(serde_json::to_value(report.combos).unwrap()),
             deck_list: Set(serde_json::to_value(report.deck_list).unwrap()),
+            inconclusive: Set(report.inconclusive),
+            incomplete_lookups: Set(Some(
+                serde_json::to_value(report.incomplete_lookups).unwrap(),
+            )),
+            created_at: Set(Some(report.created_at.unwrap_or_else(chrono::Utc::now))),
+            ruleset: Set(report.ruleset.map(|ruleset| ruleset.name().to_string())),
+            content_hash: Set(report.content_hash),
+            deck_id: Set(report.deck_id),
+            unknown_cards: Set(Some(serde_json::to_value(report.unknown_cards).unwrap())),
             ..Default::default()
         };
 
-        Report::insert(active_model)
-            .exec(&self.conn)
+        let txn = self
+            .conn
+            .begin()
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        let report_id = Report::insert(active_model)
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?
+            .last_insert_id;
+
+        let finding_rows: Vec<report_finding::ActiveModel> = findings
+            .into_iter()
+            .enumerate()
+            .flat_map(|(index, finding)| {
+                finding
+                    .cards
+                    .into_iter()
+                    .map(move |card| report_finding::ActiveModel {
+                        report_id: Set(report_id),
+                        finding_index: Set(index as i32),
+                        category: Set(finding.category.name().to_string()),
+                        card: Set(card),
+                        detail: Set(finding.detail.clone()),
+                        ..Default::default()
+                    })
+            })
+            .collect();
+        if !finding_rows.is_empty() {
+            ReportFinding::insert_many(finding_rows)
+                .exec(&txn)
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))?;
+        }
+
+        let card_rows: Vec<report_card::ActiveModel> = deck_list
+            .into_iter()
+            .map(|unit| report_card::ActiveModel {
+                report_id: Set(report_id),
+                card: Set(unit.card),
+                quantity: Set(unit.quantity as i32),
+                ..Default::default()
+            })
+            .collect();
+        if !card_rows.is_empty() {
+            ReportCard::insert_many(card_rows)
+                .exec(&txn)
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))?;
+        }
+
+        txn.commit()
             .await
             .map_err(|e| AppError::Internal(e.to_string()))?;
 
@@ -41,29 +322,870 @@ impl HistoryStore {
     }
 
     pub async fn get_all(&self) -> Result<Vec<ReportModel>, AppError> {
-        let reports = Report::find()
+        self.find(&HistoryFilter::default()).await
+    }
+
+    pub async fn find(&self, filter: &HistoryFilter) -> Result<Vec<ReportModel>, AppError> {
+        let mut query = Report::find();
+
+        if let Some(author) = &filter.author {
+            query = query.filter(lower_eq(report::Column::Author, author));
+        }
+        if let Some(name) = &filter.name {
+            query = query.filter(
+                Expr::expr(Func::lower(Expr::col(report::Column::Name))).like(like_pattern(name)),
+            );
+        }
+        if let Some(is_valid) = filter.is_valid {
+            query = query.filter(report::Column::IsValid.eq(is_valid));
+        }
+        if let Some(inconclusive) = filter.inconclusive {
+            query = query.filter(report::Column::Inconclusive.eq(inconclusive));
+        }
+        if let Some(after) = filter.created_after {
+            query = query.filter(report::Column::CreatedAt.gte(after));
+        }
+        if let Some(before) = filter.created_before {
+            query = query.filter(report::Column::CreatedAt.lt(before));
+        }
+        if let Some(ruleset) = filter.ruleset {
+            query = query.filter(report::Column::Ruleset.eq(ruleset.name()));
+        }
+
+        if let Some(card) = &filter.contains_card {
+            let cards = Query::select()
+                .column(report_card::Column::ReportId)
+                .from(ReportCard)
+                .and_where(lower_eq(report_card::Column::Card, card))
+                .to_owned();
+            query = query.filter(report::Column::Id.in_subquery(cards));
+        }
+
+        if filter.flagged_card.is_some() || filter.category.is_some() {
+            let mut findings = Query::select()
+                .column(report_finding::Column::ReportId)
+                .from(ReportFinding)
+                .to_owned();
+            if let Some(card) = &filter.flagged_card {
+                findings.and_where(lower_eq(report_finding::Column::Card, card));
+            }
+            if let Some(category) = filter.category {
+                findings.and_where(report_finding::Column::Category.eq(category.name()));
+            }
+            query = query.filter(report::Column::Id.in_subquery(findings));
+        }
+
+        query = match filter.sort {
+            HistorySort::Newest => query.order_by_desc(report::Column::Id),
+            HistorySort::Oldest => query.order_by_asc(report::Column::Id),
+            HistorySort::Name => query
+                .order_by_asc(Expr::expr(Func::lower(Expr::col(report::Column::Name))))
+                .order_by_desc(report::Column::Id),
+            HistorySort::Author => query
+                .order_by_asc(Expr::expr(Func::lower(Expr::col(report::Column::Author))))
+                .order_by_desc(report::Column::Id),
+        };
+
+        let reports = query
+            .limit(filter.limit)
+            .offset(filter.offset)
             .all(&self.conn)
             .await
             .map_err(|e| AppError::Internal(e.to_string()))?;
 
-        Ok(reports
+        Ok(reports.into_iter().map(to_report_model).collect())
+    }
+
+    pub async fn find_recent(
+        &self,
+        list: &List,
+        ruleset: &Ruleset,
+    ) -> Result<Option<ReportModel>, AppError> {
+        let Some(deck_id) = &list.public_id else {
+            return Ok(None);
+        };
+        if self.report_max_age.is_zero() {
+            return Ok(None);
+        }
+
+        let since = Utc::now() - self.report_max_age;
+        self.find_unchanged(&list.content_hash(ruleset), deck_id, since)
+            .await
+    }
+
+    pub async fn find_unchanged(
+        &self,
+        content_hash: &str,
+        deck_id: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Option<ReportModel>, AppError> {
+        let report = Report::find()
+            .filter(report::Column::ContentHash.eq(content_hash))
+            .filter(report::Column::DeckId.eq(deck_id))
+            .filter(report::Column::CreatedAt.gte(since))
+            .filter(report::Column::Inconclusive.eq(false))
+            // Fail-open reports that skipped or could not identify cards are not reusable.
+            .filter(report::Column::IncompleteLookups.eq(serde_json::json!([])))
+            .filter(report::Column::UnknownCards.eq(serde_json::json!([])))
+            .order_by_desc(report::Column::Id)
+            .one(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        Ok(report.map(to_report_model))
+    }
+
+    pub async fn totals(&self) -> Result<TotalsRow, AppError> {
+        let backend = self.conn.get_database_backend();
+        let query = Query::select()
+            .expr_as(Expr::col(report::Column::Id).count(), Alias::new("total"))
+            .expr_as(
+                Func::coalesce([passed_sum(), Expr::val(0).into()]),
+                Alias::new("passed"),
+            )
+            .expr_as(
+                Func::coalesce([inconclusive_sum(), Expr::val(0).into()]),
+                Alias::new("inconclusive"),
+            )
+            .expr_as(
+                Func::coalesce([
+                    Func::sum(Expr::case(report::Column::CreatedAt.is_null(), 1).finally(0)).into(),
+                    Expr::val(0).into(),
+                ]),
+                Alias::new("undated"),
+            )
+            .from(Report)
+            .to_owned();
+
+        TotalsRow::find_by_statement(backend.build(&query))
+            .one(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?
+            .ok_or_else(|| AppError::Internal("Report totals query returned no row".to_string()))
+    }
+
+    pub async fn pass_rate_over_time(
+        &self,
+        period: StatsPeriod,
+    ) -> Result<Vec<PassRateRow>, AppError> {
+        let backend = self.conn.get_database_backend();
+        let bucket = match backend {
+            DatabaseBackend::Postgres => Expr::cust(format!(
+                "to_char(\"created_at\" AT TIME ZONE 'UTC', '{}')",
+                period.postgres_format()
+            )),
+            _ => Expr::cust(format!(
+                "strftime('{}', \"created_at\")",
+                period.sqlite_format()
+            )),
+        };
+
+        let query = Query::select()
+            .expr_as(bucket.clone(), Alias::new("period"))
+            .expr_as(Expr::col(report::Column::Id).count(), Alias::new("total"))
+            .expr_as(passed_sum(), Alias::new("passed"))
+            .expr_as(inconclusive_sum(), Alias::new("inconclusive"))
+            .from(Report)
+            .and_where(report::Column::CreatedAt.is_not_null())
+            .add_group_by([bucket.clone()])
+            .order_by_expr(bucket, Order::Asc)
+            .to_owned();
+
+        PassRateRow::find_by_statement(backend.build(&query))
+            .all(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))
+    }
+
+    pub async fn top_flagged_cards(
+        &self,
+        category: FindingCategory,
+        limit: u64,
+    ) -> Result<Vec<FlaggedCardRow>, AppError> {
+        let backend = self.conn.get_database_backend();
+        let report_id = Expr::col((ReportFinding, report_finding::Column::ReportId));
+        let failed_report_id = Expr::case(
+            Expr::col((Report, report::Column::IsValid))
+                .eq(false)
+                .and(Expr::col((Report, report::Column::Inconclusive)).eq(false)),
+            report_id.clone(),
+        );
+
+        let query = Query::select()
+            .expr_as(
+                Expr::col((ReportFinding, report_finding::Column::Card)),
+                Alias::new("card"),
+            )
+            .expr_as(Func::count_distinct(report_id), Alias::new("decks"))
+            .expr_as(
+                Func::count_distinct(failed_report_id),
+                Alias::new("failed_decks"),
+            )
+            .from(ReportFinding)
+            .inner_join(
+                Report,
+                Expr::col((Report, report::Column::Id))
+                    .equals((ReportFinding, report_finding::Column::ReportId)),
+            )
+            .and_where(
+                Expr::col((ReportFinding, report_finding::Column::Category)).eq(category.name()),
+            )
+            .group_by_col((ReportFinding, report_finding::Column::Card))
+            .order_by(Alias::new("decks"), Order::Desc)
+            .order_by(Alias::new("card"), Order::Asc)
+            .limit(limit)
+            .to_owned();
+
+        FlaggedCardRow::find_by_statement(backend.build(&query))
+            .all(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))
+    }
+
+    pub async fn common_combos(&self, limit: u64) -> Result<Vec<ComboRow>, AppError> {
+        let backend = self.conn.get_database_backend();
+        let (first, second) = (Alias::new("first"), Alias::new("second"));
+
+        let query = Query::select()
+            .expr_as(
+                Expr::col((first.clone(), report_finding::Column::Card)),
+                Alias::new("first_card"),
+            )
+            .expr_as(
+                Expr::col((second.clone(), report_finding::Column::Card)),
+                Alias::new("second_card"),
+            )
+            .expr_as(
+                Func::count_distinct(Expr::col((first.clone(), report_finding::Column::ReportId))),
+                Alias::new("decks"),
+            )
+            .from_as(ReportFinding, first.clone())
+            .join_as(
+                JoinType::InnerJoin,
+                ReportFinding,
+                second.clone(),
+                Condition::all()
+                    .add(
+                        Expr::col((first.clone(), report_finding::Column::ReportId))
+                            .equals((second.clone(), report_finding::Column::ReportId)),
+                    )
+                    .add(
+                        Expr::col((first.clone(), report_finding::Column::FindingIndex))
+                            .equals((second.clone(), report_finding::Column::FindingIndex)),
+                    )
+                    .add(
+                        Expr::col((first.clone(), report_finding::Column::Card))
+                            .lt(Expr::col((second.clone(), report_finding::Column::Card))),
+                    ),
+            )
+            .and_where(
+                Expr::col((first.clone(), report_finding::Column::Category))
+                    .eq(FindingCategory::TwoCardCombo.name()),
+            )
+            .group_by_col((first, report_finding::Column::Card))
+            .group_by_col((second, report_finding::Column::Card))
+            .order_by(Alias::new("decks"), Order::Desc)
+            .order_by(Alias::new("first_card"), Order::Asc)
+            .limit(limit)
+            .to_owned();
+
+        ComboRow::find_by_statement(backend.build(&query))
+            .all(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))
+    }
+
+    pub async fn finding_counts(
+        &self,
+        categories: &[FindingCategory],
+    ) -> Result<Vec<FindingCountRow>, AppError> {
+        let backend = self.conn.get_database_backend();
+
+        let query = Query::select()
+            .column(report_finding::Column::Category)
+            .expr_as(
+                Expr::col(report_finding::Column::Id).count(),
+                Alias::new("findings"),
+            )
+            .from(ReportFinding)
+            .and_where(
+                report_finding::Column::Category
+                    .is_in(categories.iter().map(|category| category.name())),
+            )
+            .group_by_col(report_finding::Column::Category)
+            .to_owned();
+
+        FindingCountRow::find_by_statement(backend.build(&query))
+            .all(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))
+    }
+
+    pub async fn author_stats(&self, limit: u64) -> Result<Vec<AuthorStatsRow>, AppError> {
+        let backend = self.conn.get_database_backend();
+
+        let query = Query::select()
+            .column(report::Column::Author)
+            .expr_as(Expr::col(report::Column::Id).count(), Alias::new("reports"))
+            .expr_as(passed_sum(), Alias::new("passed"))
+            .expr_as(inconclusive_sum(), Alias::new("inconclusive"))
+            .expr_as(
+                Func::count_distinct(Expr::col(report::Column::DeckId)),
+                Alias::new("decks"),
+            )
+            .expr_as(
+                Expr::col(report::Column::CreatedAt).max(),
+                Alias::new("last_validated_at"),
+            )
+            .from(Report)
+            .group_by_col(report::Column::Author)
+            .order_by(Alias::new("reports"), Order::Desc)
+            .order_by(report::Column::Author, Order::Asc)
+            .limit(limit)
+            .to_owned();
+
+        AuthorStatsRow::find_by_statement(backend.build(&query))
+            .all(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))
+    }
+
+    /// One row per deck of the author, newest first, carrying the latest report for that
+    /// deck. Reports saved before deck ids were stored share the row with a NULL deck_id.
+    pub async fn author_decks(&self, author: &str) -> Result<Vec<AuthorDeckRow>, AppError> {
+        let backend = self.conn.get_database_backend();
+        let decks = Alias::new("decks");
+
+        let grouped = Query::select()
+            .column(report::Column::DeckId)
+            .expr_as(Expr::col(report::Column::Id).max(), Alias::new("latest_id"))
+            .expr_as(Expr::col(report::Column::Id).count(), Alias::new("checks"))
+            .expr_as(passed_sum(), Alias::new("passed"))
+            .from(Report)
+            .and_where(lower_eq(report::Column::Author, author))
+            .group_by_col(report::Column::DeckId)
+            .to_owned();
+
+        let query = Query::select()
+            .columns([
+                (Report, report::Column::DeckId),
+                (Report, report::Column::Name),
+                (Report, report::Column::Author),
+                (Report, report::Column::IsValid),
+                (Report, report::Column::Inconclusive),
+                (Report, report::Column::Ruleset),
+                (Report, report::Column::CreatedAt),
+            ])
+            .column((decks.clone(), Alias::new("checks")))
+            .column((decks.clone(), Alias::new("passed")))
+            .from(Report)
+            .join_subquery(
+                JoinType::InnerJoin,
+                grouped,
+                decks.clone(),
+                Expr::col((Report, report::Column::Id)).equals((decks, Alias::new("latest_id"))),
+            )
+            .order_by((Report, report::Column::Id), Order::Desc)
+            .to_owned();
+
+        AuthorDeckRow::find_by_statement(backend.build(&query))
+            .all(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))
+    }
+}
+
+fn passed_sum() -> SimpleExpr {
+    Func::sum(Expr::case(Expr::col(report::Column::IsValid).eq(true), 1).finally(0)).into()
+}
+
+fn inconclusive_sum() -> SimpleExpr {
+    Func::sum(Expr::case(Expr::col(report::Column::Inconclusive).eq(true), 1).finally(0)).into()
+}
+
+/// A player's checked report and whether the deck changed since the lock, or why the check failed.
+pub type CheckOutcome = Result<(ReportModel, bool), String>;
+
+#[derive(Clone)]
+pub struct EventStore {
+    conn: DatabaseConnection,
+}
+
+impl EventStore {
+    pub fn new(conn: DatabaseConnection) -> Self {
+        Self { conn }
+    }
+
+    pub async fn create_event(
+        &self,
+        name: String,
+        ruleset: String,
+    ) -> Result<event::Model, AppError> {
+        let active_model = event::ActiveModel {
+            name: Set(name),
+            ruleset: Set(ruleset),
+            locked_at: Set(None),
+            created_at: Set(chrono::Utc::now()),
+            ..Default::default()
+        };
+
+        active_model
+            .insert(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))
+    }
+
+    pub async fn get_event(&self, event_id: i32) -> Result<event::Model, AppError> {
+        Event::find_by_id(event_id)
+            .one(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?
+            .ok_or(AppError::EventNotFound(event_id))
+    }
+
+    /// Closes registration and returns the players that still need a locked deck list, so a
+    /// lock whose snapshots failed can be retried. Holds the event row like `add_player` does.
+    pub async fn lock_event(
+        &self,
+        event_id: i32,
+    ) -> Result<(event::Model, Vec<event_player::Model>), AppError> {
+        let txn = self
+            .conn
+            .begin()
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        let event = Event::find_by_id(event_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?
+            .ok_or(AppError::EventNotFound(event_id))?;
+        let players = EventPlayer::find()
+            .filter(event_player::Column::EventId.eq(event_id))
+            .filter(event_player::Column::LockedDeckList.is_null())
+            .order_by_asc(event_player::Column::Id)
+            .all(&txn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        let event = match event.locked_at {
+            Some(_) if players.is_empty() => return Err(AppError::EventLocked(event_id)),
+            Some(_) => event,
+            None => {
+                let mut active_model = event.into_active_model();
+                active_model.locked_at = Set(Some(chrono::Utc::now()));
+                active_model
+                    .update(&txn)
+                    .await
+                    .map_err(|e| AppError::Internal(e.to_string()))?
+            }
+        };
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        Ok((event, players))
+    }
+
+    pub async fn add_player(
+        &self,
+        event_id: i32,
+        player_name: String,
+        deck_id: String,
+    ) -> Result<event_player::Model, AppError> {
+        let txn = self
+            .conn
+            .begin()
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        let event = Event::find_by_id(event_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?
+            .ok_or(AppError::EventNotFound(event_id))?;
+        if event.locked_at.is_some() {
+            return Err(AppError::EventLocked(event_id));
+        }
+
+        let active_model = event_player::ActiveModel {
+            event_id: Set(event_id),
+            player_name: Set(player_name),
+            deck_id: Set(deck_id),
+            locked_deck_list: Set(None),
+            is_valid: Set(None),
+            report: Set(None),
+            error: Set(None),
+            changed_after_lock: Set(false),
+            checked_at: Set(None),
+            ..Default::default()
+        };
+        let player = active_model
+            .insert(&txn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        Ok(player)
+    }
+
+    pub async fn get_players(&self, event_id: i32) -> Result<Vec<event_player::Model>, AppError> {
+        EventPlayer::find()
+            .filter(event_player::Column::EventId.eq(event_id))
+            .order_by_asc(event_player::Column::Id)
+            .all(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))
+    }
+
+    pub async fn lock_players(
+        &self,
+        snapshots: Vec<(event_player::Model, Result<Vec<CardListUnit>, String>)>,
+    ) -> Result<(), AppError> {
+        let txn = self
+            .conn
+            .begin()
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        for (player, snapshot) in snapshots {
+            let mut active_model = player.into_active_model();
+            match snapshot {
+                Ok(deck_list) => {
+                    active_model.locked_deck_list =
+                        Set(Some(serde_json::to_value(deck_list).unwrap()));
+                    active_model.error = Set(None);
+                }
+                Err(error) => {
+                    active_model.locked_deck_list = Set(None);
+                    active_model.error = Set(Some(error));
+                }
+            }
+            active_model.changed_after_lock = Set(false);
+
+            active_model
+                .update(&txn)
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))
+    }
+
+    pub async fn record_checks(
+        &self,
+        outcomes: Vec<(event_player::Model, CheckOutcome)>,
+    ) -> Result<(), AppError> {
+        let txn = self
+            .conn
+            .begin()
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        let checked_at = chrono::Utc::now();
+        for (player, outcome) in outcomes {
+            let mut active_model = player.into_active_model();
+            match outcome {
+                Ok((report, changed_after_lock)) => {
+                    active_model.is_valid = Set(Some(report.is_valid));
+                    active_model.report = Set(Some(serde_json::to_value(report).unwrap()));
+                    active_model.error = Set(None);
+                    active_model.changed_after_lock = Set(changed_after_lock);
+                }
+                Err(error) => {
+                    active_model.is_valid = Set(None);
+                    active_model.report = Set(None);
+                    active_model.error = Set(Some(error));
+                    active_model.changed_after_lock = Set(false);
+                }
+            }
+            active_model.checked_at = Set(Some(checked_at));
+
+            active_model
+                .update(&txn)
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))
+    }
+}
+
+#[derive(Clone)]
+pub struct JobStore {
+    conn: DatabaseConnection,
+}
+
+impl JobStore {
+    pub fn new(conn: DatabaseConnection) -> Self {
+        Self { conn }
+    }
+
+    pub async fn create_job(
+        &self,
+        ruleset: String,
+        deck_ids: Vec<String>,
+    ) -> Result<job::Model, AppError> {
+        let txn = self
+            .conn
+            .begin()
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        let now = chrono::Utc::now();
+        let job = job::ActiveModel {
+            ruleset: Set(ruleset),
+            created_at: Set(now),
+            finished_at: Set(None),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        let decks: Vec<job_deck::ActiveModel> = deck_ids
+            .into_iter()
+            .map(|deck_id| job_deck::ActiveModel {
+                job_id: Set(job.id),
+                deck_id: Set(deck_id),
+                status: Set(JobDeckStatus::Pending),
+                is_valid: Set(None),
+                report: Set(None),
+                error: Set(None),
+                updated_at: Set(now),
+                ..Default::default()
+            })
+            .collect();
+
+        if !decks.is_empty() {
+            JobDeck::insert_many(decks)
+                .exec(&txn)
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        Ok(job)
+    }
+
+    pub async fn get_job(&self, job_id: i32) -> Result<job::Model, AppError> {
+        Job::find_by_id(job_id)
+            .one(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?
+            .ok_or(AppError::JobNotFound(job_id))
+    }
+
+    pub async fn get_decks(&self, job_id: i32) -> Result<Vec<job_deck::Model>, AppError> {
+        JobDeck::find()
+            .filter(job_deck::Column::JobId.eq(job_id))
+            .order_by_asc(job_deck::Column::Id)
+            .all(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))
+    }
+
+    pub async fn get_unfinished(&self) -> Result<Vec<job::Model>, AppError> {
+        Job::find()
+            .filter(job::Column::FinishedAt.is_null())
+            .order_by_asc(job::Column::Id)
+            .all(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))
+    }
+
+    pub async fn reset_running(&self, job_id: i32) -> Result<(), AppError> {
+        JobDeck::update_many()
+            .col_expr(
+                job_deck::Column::Status,
+                sea_orm::sea_query::Expr::value(JobDeckStatus::Pending),
+            )
+            .filter(job_deck::Column::JobId.eq(job_id))
+            .filter(job_deck::Column::Status.eq(JobDeckStatus::Running))
+            .exec(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        Ok(())
+    }
+
+    pub async fn update_deck(
+        &self,
+        deck: job_deck::Model,
+        status: JobDeckStatus,
+        report: Option<&ReportModel>,
+        error: Option<serde_json::Value>,
+    ) -> Result<job_deck::Model, AppError> {
+        let mut active_model = deck.into_active_model();
+        active_model.status = Set(status);
+        active_model.is_valid = Set(report.filter(|r| !r.inconclusive).map(|r| r.is_valid));
+        active_model.report = Set(report.map(|r| serde_json::to_value(r).unwrap()));
+        active_model.error = Set(error);
+        active_model.updated_at = Set(chrono::Utc::now());
+
+        active_model
+            .update(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))
+    }
+
+    pub async fn finish_job(
+        &self,
+        job: job::Model,
+        error: Option<String>,
+    ) -> Result<job::Model, AppError> {
+        if error.is_some() {
+            JobDeck::update_many()
+                .col_expr(
+                    job_deck::Column::Status,
+                    sea_orm::sea_query::Expr::value(JobDeckStatus::Failed),
+                )
+                .filter(job_deck::Column::JobId.eq(job.id))
+                .filter(job_deck::Column::Status.eq(JobDeckStatus::Running))
+                .exec(&self.conn)
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))?;
+        }
+
+        let mut active_model = job.into_active_model();
+        active_model.finished_at = Set(Some(chrono::Utc::now()));
+        active_model.error = Set(error);
+
+        active_model
+            .update(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))
+    }
+}
+
+#[derive(Clone)]
+pub struct CacheEntryStore {
+    conn: DatabaseConnection,
+}
+
+impl CacheEntryStore {
+    pub fn new(conn: DatabaseConnection) -> Self {
+        Self { conn }
+    }
+}
+
+#[async_trait]
+impl CacheBackend for CacheEntryStore {
+    async fn get(&self, source: CacheSource, key: &str) -> Result<Option<CachedValue>, AppError> {
+        let entry = CacheEntry::find_by_id((source.name().to_string(), key.to_string()))
+            .filter(cache_entry::Column::ExpiresAt.gt(chrono::Utc::now()))
+            .one(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        Ok(entry.map(|entry| CachedValue {
+            value: entry.value,
+            expires_at: entry.expires_at,
+        }))
+    }
+
+    async fn insert(
+        &self,
+        source: CacheSource,
+        key: String,
+        entry: CachedValue,
+    ) -> Result<(), AppError> {
+        let active_model = cache_entry::ActiveModel {
+            source: Set(source.name().to_string()),
+            key: Set(key),
+            value: Set(entry.value),
+            created_at: Set(chrono::Utc::now()),
+            expires_at: Set(entry.expires_at),
+        };
+
+        CacheEntry::insert(active_model)
+            .on_conflict(
+                OnConflict::columns([cache_entry::Column::Source, cache_entry::Column::Key])
+                    .update_columns([
+                        cache_entry::Column::Value,
+                        cache_entry::Column::CreatedAt,
+                        cache_entry::Column::ExpiresAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec_without_returning(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn count(&self, source: CacheSource) -> Result<u64, AppError> {
+        CacheEntry::find()
+            .filter(cache_entry::Column::Source.eq(source.name()))
+            .filter(cache_entry::Column::ExpiresAt.gt(chrono::Utc::now()))
+            .count(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))
+    }
+
+    async fn entries(
+        &self,
+        source: CacheSource,
+        limit: u64,
+    ) -> Result<Vec<CacheEntrySummary>, AppError> {
+        let entries = CacheEntry::find()
+            .select_only()
+            .columns([
+                cache_entry::Column::Key,
+                cache_entry::Column::CreatedAt,
+                cache_entry::Column::ExpiresAt,
+            ])
+            .filter(cache_entry::Column::Source.eq(source.name()))
+            .order_by_desc(cache_entry::Column::CreatedAt)
+            .limit(limit)
+            .into_tuple::<(
+                String,
+                chrono::DateTime<chrono::Utc>,
+                chrono::DateTime<chrono::Utc>,
+            )>()
+            .all(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        Ok(entries
             .into_iter()
-            .map(|r| ReportModel {
-                is_valid: r.is_valid,
-                name: r.name,
-                author: r.author,
-                non_land_tutors: serde_json::from_value(r.non_land_tutors).unwrap_or_default(),
-                mass_land_denial_cards: serde_json::from_value(r.mass_land_denial_cards)
-                    .unwrap_or_default(),
-                commander_tutors: serde_json::from_value(r.commander_tutors).unwrap_or_default(),
-                two_card_combos: serde_json::from_value(r.two_card_combos).unwrap_or_default(),
-                gamechangers: serde_json::from_value(r.gamechangers).unwrap_or_default(),
-                infinite_turns_combos: serde_json::from_value(r.infinite_turns_combos)
-                    .unwrap_or_default(),
-                combos: serde_json::from_value(r.combos).unwrap_or_default(),
-                deck_list: serde_json::from_value(r.deck_list).unwrap_or_default(),
+            .map(|(key, created_at, expires_at)| CacheEntrySummary {
+                key,
+                created_at,
+                expires_at,
             })
-            .rev()
             .collect())
     }
+
+    async fn purge(&self, source: CacheSource) -> Result<u64, AppError> {
+        let result = CacheEntry::delete_many()
+            .filter(cache_entry::Column::Source.eq(source.name()))
+            .exec(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+        Ok(result.rows_affected)
+    }
+
+    async fn purge_expired(&self) -> Result<u64, AppError> {
+        let result = CacheEntry::delete_many()
+            .filter(cache_entry::Column::ExpiresAt.lte(chrono::Utc::now()))
+            .exec(&self.conn)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+        Ok(result.rows_affected)
+    }
 }
@@ -1,56 +1,177 @@
-use serde::{Deserialize, Serialize};
+use crate::errors::ErrorBody;
+#[cfg(feature = "server")]
+use crate::jobs::JobStatus;
+use crate::models::Report;
+use crate::ruleset::Ruleset;
+use crate::validation_results::Finding;
+use serde::Serialize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use tokio::sync::broadcast;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+pub const PROTOCOL_VERSION: u32 = 1;
+pub const PROGRESS_CHANNEL_CAPACITY: usize = 1024;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    Started {
+        ruleset: Ruleset,
+    },
+    DeckFetched {
+        name: String,
+        author: String,
+        cards: usize,
+    },
+    CardChecked {
+        validator: String,
+        card: String,
+        checked: usize,
+        total: usize,
+    },
+    ValidatorDone {
+        validator: String,
+        completed: usize,
+        total: usize,
+    },
+    Finding(Finding),
+    Completed {
+        report: Box<Report>,
+    },
+    Error(ErrorBody),
+    JobProgress {
+        completed: usize,
+        total: usize,
+    },
+    #[cfg(feature = "server")]
+    JobFinished(JobStatus),
+    Cancelled,
+    Pong,
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct ProgressMessage {
-    pub r#type: String,
-    pub validator: String,
-    pub completed: usize,
-    pub total: usize,
-    pub status: String,
+    pub version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seq: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deck_id: Option<String>,
+    #[serde(flatten)]
+    pub event: ProgressEvent,
 }
 
+impl ProgressMessage {
+    pub fn new(deck_id: Option<String>, event: ProgressEvent) -> Self {
+        Self {
+            version: PROTOCOL_VERSION,
+            seq: None,
+            deck_id,
+            event,
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self.event,
+            ProgressEvent::Completed { .. } | ProgressEvent::Error(_)
+        )
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+#[derive(Clone)]
 pub struct ProgressTracker {
     sender: broadcast::Sender<ProgressMessage>,
+    deck_id: Option<String>,
     total_validators: usize,
-    completed_validators: usize,
+    completed_validators: Arc<AtomicUsize>,
+    replay_log: Option<Arc<Mutex<Vec<ProgressMessage>>>>,
 }
 
 impl ProgressTracker {
     pub fn new(total_validators: usize) -> Self {
-        let (sender, _) = broadcast::channel(100);
+        let (sender, _) = broadcast::channel(PROGRESS_CHANNEL_CAPACITY);
         Self {
             sender,
+            deck_id: None,
             total_validators,
-            completed_validators: 0,
+            completed_validators: Arc::new(AtomicUsize::new(0)),
+            replay_log: None,
         }
     }
 
-    pub fn subscribe(&self) -> broadcast::Receiver<ProgressMessage> {
-        self.sender.subscribe()
+    pub fn for_deck(
+        sender: broadcast::Sender<ProgressMessage>,
+        deck_id: String,
+        total_validators: usize,
+    ) -> Self {
+        Self {
+            sender,
+            deck_id: Some(deck_id),
+            total_validators,
+            completed_validators: Arc::new(AtomicUsize::new(0)),
+            replay_log: None,
+        }
     }
 
-    pub fn update(&mut self, validator_name: String, status: String) {
-        self.completed_validators += 1;
-        let message = ProgressMessage {
-            r#type: "progress".to_string(),
-            validator: validator_name,
-            completed: self.completed_validators,
-            total: self.total_validators,
-            status,
-        };
-        let _ = self.sender.send(message);
+    pub fn with_replay(mut self) -> Self {
+        self.replay_log = Some(Arc::new(Mutex::new(Vec::new())));
+        self
+    }
+
+    pub fn replay(&self) -> (Vec<ProgressMessage>, broadcast::Receiver<ProgressMessage>) {
+        match &self.replay_log {
+            Some(log) => {
+                let log = log.lock().unwrap();
+                (log.clone(), self.sender.subscribe())
+            }
+            None => (Vec::new(), self.sender.subscribe()),
+        }
+    }
+
+    pub fn send(&self, event: ProgressEvent) {
+        let mut message = ProgressMessage::new(self.deck_id.clone(), event);
+        match &self.replay_log {
+            Some(log) => {
+                let mut log = log.lock().unwrap();
+                message.seq = Some(log.len());
+                log.push(message.clone());
+                let _ = self.sender.send(message);
+            }
+            None => {
+                let _ = self.sender.send(message);
+            }
+        }
+    }
+
+    pub fn card_checked(&self, validator: &str, card: &str, checked: usize, total: usize) {
+        self.send(ProgressEvent::CardChecked {
+            validator: validator.to_string(),
+            card: card.to_string(),
+            checked,
+            total,
+        });
     }
 
-    #[allow(dead_code)]
-    pub fn mark_complete(&self, report_json: String) {
-        let message = ProgressMessage {
-            r#type: "complete".to_string(),
-            validator: String::new(),
-            completed: self.total_validators,
+    pub fn validator_done(&self, validator: &str) {
+        let completed = self.completed_validators.fetch_add(1, Ordering::SeqCst) + 1;
+        self.send(ProgressEvent::ValidatorDone {
+            validator: validator.to_string(),
+            completed,
             total: self.total_validators,
-            status: report_json,
-        };
-        let _ = self.sender.send(message);
+        });
+    }
+
+    pub fn complete(&self, report: Report) {
+        self.send(ProgressEvent::Completed {
+            report: Box::new(report),
+        });
+    }
+
+    pub fn fail(&self, error: ErrorBody) {
+        self.send(ProgressEvent::Error(error));
     }
 }
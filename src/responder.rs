@@ -0,0 +1,68 @@
+use crate::errors::{AppError, ErrorBody};
+use crate::request_id::RequestId;
+use rocket::Request;
+use rocket::http::{ContentType, Status};
+use rocket::response::{Responder, Response, Result};
+use rocket::serde::json::Json;
+use std::io::Cursor;
+
+impl AppError {
+    pub fn status(&self) -> Status {
+        match self {
+            AppError::MoxfieldApiError(e) if e.is_timeout() => Status::GatewayTimeout,
+            AppError::MoxfieldApiError(_) => Status::BadGateway,
+            AppError::DeckNotFound(_) => Status::NotFound,
+            AppError::DeckPrivate(_) => Status::Forbidden,
+            AppError::InvalidDeckId(_) => Status::BadRequest,
+            AppError::MoxfieldRateLimited { .. } => Status::TooManyRequests,
+            AppError::SpellbookApiError(_) => Status::BadGateway,
+            AppError::Configuration(_) => Status::InternalServerError,
+            AppError::AuthorNotFound(_) => Status::NotFound,
+            AppError::EventNotFound(_) => Status::NotFound,
+            AppError::EventLocked(_) => Status::Conflict,
+            AppError::JobNotFound(_) => Status::NotFound,
+            AppError::Unauthorized => Status::Unauthorized,
+            AppError::InvalidRequest(_) => Status::BadRequest,
+            AppError::Internal(_) => Status::InternalServerError,
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> Responder<'r, 'static> for AppError {
+    fn respond_to(self, request: &'r Request<'_>) -> Result<'static> {
+        let request_id = RequestId::of(request).0.clone();
+        let body = self.to_body(Some(request_id));
+        let json = serde_json::to_string(&body).map_err(|_| Status::InternalServerError)?;
+
+        let mut response = Response::build();
+        response
+            .status(self.status())
+            .header(ContentType::JSON)
+            .sized_body(json.len(), Cursor::new(json));
+        if let Some(seconds) = body.retry_after {
+            response.raw_header("Retry-After", seconds.to_string());
+        }
+        response.ok()
+    }
+}
+
+#[catch(default)]
+pub fn json_catcher(status: Status, request: &Request<'_>) -> (Status, Json<ErrorBody>) {
+    let reason = status.reason().unwrap_or("Error");
+    let request_id = Some(RequestId::of(request).0.clone());
+    let body = match status.code {
+        401 => AppError::Unauthorized.to_body(request_id),
+        404 => ErrorBody {
+            code: "not_found",
+            message: format!("No route for {} {}", request.method(), request.uri().path()),
+            upstream: None,
+            retry_after: None,
+            request_id,
+        },
+        400..=499 => AppError::InvalidRequest(reason.to_string()).to_body(request_id),
+        _ => AppError::Internal(reason.to_string()).to_body(request_id),
+    };
+
+    (status, Json(body))
+}
@@ -1,109 +1,220 @@
-use crate::errors::AppError;
+use crate::config::AppConfig;
+use crate::errors::{AppError, ErrorBody};
+use crate::persistence::{HistoryFilter, HistorySort};
+use crate::request_id::RequestId;
+use crate::ruleset::Ruleset;
+use crate::validation_results::FindingCategory;
+use chrono::{DateTime, Days, NaiveDate, Utc};
 use futures::stream::{self, StreamExt};
 use rocket::{State, serde::json::Json};
+use serde::Serialize;
 
-use crate::{
-    models::{List, Report},
-    persistence::HistoryStore,
-    validators::{
-        CommanderTutorValidator, GamechangerValidator, InfiniteTurnsValidator,
-        MassLandDenialValidator, NonLandTutorValidator, TwoCardComboValidator,
-    },
-};
-
-#[get("/history")]
-pub async fn get_history(store: &State<HistoryStore>) -> Result<Json<Vec<Report>>, AppError> {
-    let reports = store.get_all().await?;
+use crate::{models::Report, moxfield::fetch_list, persistence::HistoryStore, upstream::Upstream};
+
+const MAX_HISTORY_LIMIT: u64 = 1000;
+
+#[derive(FromForm, Debug, Default)]
+pub struct HistoryQuery {
+    pub author: Option<String>,
+    pub name: Option<String>,
+    pub valid: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub ruleset: Option<String>,
+    pub card: Option<String>,
+    pub flagged: Option<String>,
+    pub category: Option<String>,
+    pub sort: Option<String>,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.trim().is_empty())
+}
+
+fn parse_date(value: &str, end_of_range: bool) -> Result<DateTime<Utc>, AppError> {
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Ok(date.with_timezone(&Utc));
+    }
+
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        AppError::InvalidRequest(format!(
+            "Invalid date {}, expected YYYY-MM-DD or RFC 3339",
+            value
+        ))
+    })?;
+    let date = if end_of_range {
+        date.checked_add_days(Days::new(1)).unwrap_or(date)
+    } else {
+        date
+    };
+    Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+}
+
+impl HistoryQuery {
+    pub fn into_filter(self) -> Result<HistoryFilter, AppError> {
+        let (is_valid, inconclusive) = match non_empty(self.valid).as_deref() {
+            None => (None, None),
+            Some("true") => (Some(true), None),
+            Some("false") => (Some(false), Some(false)),
+            Some("inconclusive") => (None, Some(true)),
+            Some(value) => {
+                return Err(AppError::InvalidRequest(format!(
+                    "Invalid validity filter {}, expected true, false or inconclusive",
+                    value
+                )));
+            }
+        };
+        let ruleset = non_empty(self.ruleset)
+            .map(|name| {
+                Ruleset::from_name(&name)
+                    .ok_or_else(|| AppError::InvalidRequest(format!("Unknown ruleset {}", name)))
+            })
+            .transpose()?;
+        let category = non_empty(self.category)
+            .map(|name| {
+                FindingCategory::from_name(&name)
+                    .ok_or_else(|| AppError::InvalidRequest(format!("Unknown category {}", name)))
+            })
+            .transpose()?;
+        let sort = match non_empty(self.sort) {
+            Some(name) => HistorySort::from_name(&name)
+                .ok_or_else(|| AppError::InvalidRequest(format!("Unknown sort {}", name)))?,
+            None => HistorySort::default(),
+        };
+
+        Ok(HistoryFilter {
+            author: non_empty(self.author),
+            name: non_empty(self.name),
+            is_valid,
+            inconclusive,
+            created_after: non_empty(self.from)
+                .map(|date| parse_date(&date, false))
+                .transpose()?,
+            created_before: non_empty(self.to)
+                .map(|date| parse_date(&date, true))
+                .transpose()?,
+            ruleset,
+            contains_card: non_empty(self.card),
+            flagged_card: non_empty(self.flagged),
+            category,
+            sort,
+            limit: self.limit.map(|limit| limit.clamp(1, MAX_HISTORY_LIMIT)),
+            offset: self.offset,
+        })
+    }
+}
+
+#[get("/history?<query..>")]
+pub async fn get_history(
+    query: HistoryQuery,
+    store: &State<HistoryStore>,
+) -> Result<Json<Vec<Report>>, AppError> {
+    let reports = store.find(&query.into_filter()?).await?;
     Ok(Json(reports))
 }
 
-#[get("/validate/<id>")]
+#[get("/validate/<id>?<fresh>")]
 pub async fn validate(
     id: &str,
-    client: &State<reqwest::Client>,
+    fresh: Option<bool>,
+    upstream: &State<Upstream>,
     store: &State<HistoryStore>,
+    config: &State<AppConfig>,
 ) -> Result<Json<Report>, AppError> {
-    let user_agent = std::env::var("MOXFIELD_USER_AGENT").map_err(AppError::EnvVarMissing)?;
-    let response: List = client
-        .get("https://api2.moxfield.com/v3/decks/all/".to_string() + id)
-        .header("User-Agent", &user_agent)
-        .header("Accept", "application/json")
-        .send()
-        .await?
-        .json()
-        .await?;
-
-    let report = response
-        .validate(
-            client,
-            vec![
-                Box::new(MassLandDenialValidator),
-                Box::new(NonLandTutorValidator),
-                Box::new(CommanderTutorValidator),
-                Box::new(GamechangerValidator),
-                Box::new(InfiniteTurnsValidator),
-                Box::new(TwoCardComboValidator),
-            ],
-        )
-        .await?;
+    let list = fetch_list(upstream, id).await?;
+    let ruleset = config.default_ruleset;
+
+    if !fresh.unwrap_or(false)
+        && let Some(report) = store.find_recent(&list, &ruleset).await?
+    {
+        return Ok(Json(report));
+    }
+
+    let report = list.validate(upstream, &ruleset).await?;
 
     store.save(report.clone()).await?;
 
     Ok(Json(report))
 }
 
-#[post("/validate/batch", data = "<id_lists>")]
+#[derive(Serialize, Debug)]
+pub struct BatchItem {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<Report>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorBody>,
+}
+
+#[post("/validate/batch?<concurrency>", data = "<id_lists>")]
 pub async fn validate_batch(
     id_lists: Json<Vec<String>>,
-    client: &State<reqwest::Client>,
+    concurrency: Option<usize>,
+    upstream: &State<Upstream>,
     store: &State<HistoryStore>,
-) -> Result<Json<Vec<Report>>, AppError> {
-    let reports = stream::iter(id_lists.into_inner())
+    config: &State<AppConfig>,
+    request_id: &RequestId,
+) -> Result<Json<Vec<BatchItem>>, AppError> {
+    let items = validate_ids(
+        id_lists.into_inner(),
+        config.batch_concurrency(concurrency),
+        config.default_ruleset,
+        upstream,
+        store,
+        request_id,
+    )
+    .await?;
+
+    Ok(Json(items))
+}
+
+pub async fn validate_ids(
+    ids: Vec<String>,
+    concurrency: usize,
+    ruleset: Ruleset,
+    upstream: &Upstream,
+    store: &HistoryStore,
+    request_id: &RequestId,
+) -> Result<Vec<BatchItem>, AppError> {
+    let results = stream::iter(ids)
         .map(|id| async move {
-            let user_agent =
-                std::env::var("MOXFIELD_USER_AGENT").map_err(AppError::EnvVarMissing)?;
-            let response: List = client
-                .get("https://api2.moxfield.com/v3/decks/all/".to_string() + &id)
-                .header("User-Agent", &user_agent)
-                .header("Accept", "application/json")
-                .send()
-                .await?
-                .json()
-                .await?;
-
-            let report = response
-                .validate(
-                    client,
-                    vec![
-                        Box::new(MassLandDenialValidator),
-                        Box::new(NonLandTutorValidator),
-                        Box::new(CommanderTutorValidator),
-                        Box::new(GamechangerValidator),
-                        Box::new(InfiniteTurnsValidator),
-                        Box::new(TwoCardComboValidator),
-                    ],
-                )
-                .await?;
-            Ok::<Report, AppError>(report)
+            let result = async {
+                let list = fetch_list(upstream, &id).await?;
+                if let Some(report) = store.find_recent(&list, &ruleset).await? {
+                    return Ok((report, true));
+                }
+                Ok::<_, AppError>((list.validate(upstream, &ruleset).await?, false))
+            }
+            .await;
+            (id, result)
         })
-        .buffer_unordered(10)
+        .buffered(concurrency)
         .collect::<Vec<_>>()
         .await;
 
-    let successful_reports: Vec<Report> = reports
-        .into_iter()
-        .filter_map(|res| match res {
-            Ok(report) => Some(report),
-            Err(e) => {
-                eprintln!("Error validating list: {}", e);
-                None
+    let mut items = Vec::with_capacity(results.len());
+    for (id, result) in results {
+        let item = match result {
+            Ok((report, cached)) => {
+                if !cached {
+                    store.save(report.clone()).await?;
+                }
+                BatchItem {
+                    id,
+                    report: Some(report),
+                    error: None,
+                }
             }
-        })
-        .collect();
-
-    for report in &successful_reports {
-        store.save(report.clone()).await?;
+            Err(e) => BatchItem {
+                id,
+                report: None,
+                error: Some(e.to_body(Some(request_id.0.clone()))),
+            },
+        };
+        items.push(item);
     }
 
-    Ok(Json(successful_reports))
+    Ok(items)
 }
@@ -0,0 +1,67 @@
+use crate::validators::{
+    CommanderTutorValidator, GamechangerValidator, InfiniteTurnsValidator, MassLandDenialValidator,
+    NonLandTutorValidator, TwoCardComboValidator, Validator,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Ruleset {
+    #[default]
+    House,
+    Bracket2,
+    Bracket3,
+}
+
+impl Ruleset {
+    pub const ALL: [Ruleset; 3] = [Ruleset::House, Ruleset::Bracket2, Ruleset::Bracket3];
+    pub const VERSION: u32 = 2;
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Ruleset::House => "house",
+            Ruleset::Bracket2 => "bracket2",
+            Ruleset::Bracket3 => "bracket3",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|ruleset| ruleset.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn max_non_land_tutors(&self) -> Option<usize> {
+        match self {
+            Ruleset::House => Some(3),
+            Ruleset::Bracket2 => Some(3),
+            Ruleset::Bracket3 => None,
+        }
+    }
+
+    pub fn max_gamechangers(&self) -> usize {
+        match self {
+            Ruleset::House | Ruleset::Bracket2 => 0,
+            Ruleset::Bracket3 => 3,
+        }
+    }
+
+    pub fn allows_commander_tutors(&self) -> bool {
+        !matches!(self, Ruleset::House)
+    }
+
+    pub fn allows_two_card_combos(&self) -> bool {
+        false
+    }
+
+    pub fn validators(&self) -> Vec<Box<dyn Validator>> {
+        vec![
+            Box::new(MassLandDenialValidator),
+            Box::new(NonLandTutorValidator),
+            Box::new(CommanderTutorValidator),
+            Box::new(GamechangerValidator),
+            Box::new(InfiniteTurnsValidator),
+            Box::new(TwoCardComboValidator),
+        ]
+    }
+}
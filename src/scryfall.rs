@@ -0,0 +1,137 @@
+use reqwest::Url;
+
+const FACE_SEPARATOR: &str = "//";
+
+pub fn face_names(name: &str) -> impl Iterator<Item = &str> {
+    name.split(FACE_SEPARATOR)
+        .map(str::trim)
+        .filter(|face| !face.is_empty())
+}
+
+pub fn front_face(name: &str) -> &str {
+    face_names(name).next().unwrap_or(name.trim())
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SearchQuery {
+    terms: Vec<String>,
+}
+
+impl SearchQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn exact_name(mut self, name: &str) -> Self {
+        let escaped = front_face(name).replace('\\', "\\\\").replace('"', "\\\"");
+        self.terms.push(format!("!\"{}\"", escaped));
+        self
+    }
+
+    pub fn include_extras(mut self) -> Self {
+        self.terms.push("include:extras".to_string());
+        self
+    }
+
+    pub fn to_query(&self) -> String {
+        self.terms.join(" ")
+    }
+
+    pub fn to_url(&self, api: &str) -> Url {
+        Url::parse_with_params(&format!("{}/cards/search", api), &[("q", self.to_query())])
+            .expect("Scryfall search URL is valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const API: &str = "https://api.scryfall.com";
+
+    fn search_url(name: &str) -> String {
+        SearchQuery::new().exact_name(name).to_url(API).to_string()
+    }
+
+    #[test]
+    fn escapes_quotes_in_exact_names() {
+        let query = SearchQuery::new()
+            .exact_name("Kongming, \"Sleeping Dragon\"")
+            .include_extras();
+
+        assert_eq!(
+            query.to_query(),
+            r#"!"Kongming, \"Sleeping Dragon\"" include:extras"#
+        );
+        assert_eq!(
+            query.to_url(API).as_str(),
+            "https://api.scryfall.com/cards/search?q=%21%22Kongming%2C+%5C%22Sleeping+Dragon%5C%22%22+include%3Aextras"
+        );
+    }
+
+    #[test]
+    fn encodes_reserved_characters() {
+        assert_eq!(
+            search_url("Minsc & Boo, Timeless Heroes"),
+            "https://api.scryfall.com/cards/search?q=%21%22Minsc+%26+Boo%2C+Timeless+Heroes%22"
+        );
+        assert_eq!(
+            search_url("+2 Mace"),
+            "https://api.scryfall.com/cards/search?q=%21%22%2B2+Mace%22"
+        );
+    }
+
+    #[test]
+    fn encodes_non_ascii_names() {
+        assert_eq!(
+            search_url("Lim-Dûl's Vault"),
+            "https://api.scryfall.com/cards/search?q=%21%22Lim-D%C3%BBl%27s+Vault%22"
+        );
+        assert_eq!(
+            search_url("Æther Vial"),
+            "https://api.scryfall.com/cards/search?q=%21%22%C3%86ther+Vial%22"
+        );
+    }
+
+    #[test]
+    fn searches_split_and_modal_cards_by_front_face() {
+        assert_eq!(
+            search_url("Fire // Ice"),
+            "https://api.scryfall.com/cards/search?q=%21%22Fire%22"
+        );
+        assert_eq!(
+            search_url("Valki, God of Lies // Tibalt, Cosmic Impostor"),
+            "https://api.scryfall.com/cards/search?q=%21%22Valki%2C+God+of+Lies%22"
+        );
+    }
+
+    #[test]
+    fn splits_faces() {
+        assert_eq!(
+            face_names("Fire // Ice").collect::<Vec<_>>(),
+            ["Fire", "Ice"]
+        );
+        assert_eq!(
+            face_names("Valki, God of Lies // Tibalt, Cosmic Impostor").collect::<Vec<_>>(),
+            ["Valki, God of Lies", "Tibalt, Cosmic Impostor"]
+        );
+        assert_eq!(
+            face_names("Who // What // When // Where // Why").collect::<Vec<_>>(),
+            ["Who", "What", "When", "Where", "Why"]
+        );
+        assert_eq!(face_names("Fire//Ice").collect::<Vec<_>>(), ["Fire", "Ice"]);
+        assert_eq!(face_names(" Sol Ring ").collect::<Vec<_>>(), ["Sol Ring"]);
+    }
+
+    #[test]
+    fn keeps_un_set_names_intact() {
+        assert_eq!(front_face("_____"), "_____");
+        assert_eq!(
+            search_url("_____"),
+            "https://api.scryfall.com/cards/search?q=%21%22_____%22"
+        );
+        assert_eq!(front_face("Who // What // When // Where // Why"), "Who");
+        assert_eq!(front_face("+2 Mace"), "+2 Mace");
+        assert_eq!(front_face("  "), "");
+    }
+}
@@ -0,0 +1,103 @@
+use crate::cache::LookupCache;
+use crate::config::AppConfig;
+use crate::errors::AppError;
+use crate::http::HttpClient;
+use crate::inflight::InFlightValidations;
+use crate::jobs::JobRunner;
+use crate::migrator::Migrator;
+use crate::persistence::{CacheEntryStore, EventStore, HistoryStore, JobStore};
+use crate::request_id::RequestIdFairing;
+use crate::upstream::Upstream;
+use crate::{admin, authors, events, jobs, responder, routes, sse, stats, ws};
+use rocket::figment::providers::Env;
+use rocket::{Build, Rocket};
+use sea_orm::Database;
+use sea_orm_migration::MigratorTrait;
+use std::time::Duration;
+
+pub fn load_config() -> Result<AppConfig, AppError> {
+    let config = extract_config()?;
+    config.validate()?;
+    Ok(config)
+}
+
+pub fn load_cli_config() -> Result<AppConfig, AppError> {
+    let config = extract_config()?;
+    config.validate_cli()?;
+    Ok(config)
+}
+
+fn extract_config() -> Result<AppConfig, AppError> {
+    rocket::Config::figment()
+        .merge(Env::raw().only(&AppConfig::ENV_KEYS).global())
+        .extract()
+        .map_err(|e| AppError::Configuration(e.to_string()))
+}
+
+pub async fn rocket(config: AppConfig) -> Rocket<Build> {
+    let conn = Database::connect(&config.database_url)
+        .await
+        .expect("Failed to connect to database");
+
+    Migrator::up(&conn, None).await.expect("Migration failed");
+
+    let cache =
+        LookupCache::new(config.cache_config()).with_backend(CacheEntryStore::new(conn.clone()));
+    cache
+        .purge_expired()
+        .await
+        .expect("Failed to purge expired cache entries");
+    let upstream = Upstream::new(
+        HttpClient::new(config.http_config()),
+        cache,
+        config.upstream_config(),
+    );
+
+    let history_store = HistoryStore::new(conn.clone())
+        .with_report_max_age(Duration::from_secs(config.report_max_age_secs));
+    let event_store = EventStore::new(conn.clone());
+    let job_runner = JobRunner::new(
+        upstream.clone(),
+        JobStore::new(conn),
+        history_store.clone(),
+        config.batch_concurrency,
+    );
+    let inflight = InFlightValidations::new(upstream.clone(), history_store.clone());
+
+    job_runner.resume().await.expect("Failed to resume jobs");
+
+    rocket::build()
+        .attach(RequestIdFairing)
+        .manage(config)
+        .manage(upstream)
+        .manage(history_store)
+        .manage(event_store)
+        .manage(job_runner)
+        .manage(inflight)
+        .register("/", catchers![responder::json_catcher])
+        .mount(
+            "/",
+            routes![
+                routes::validate,
+                routes::validate_batch,
+                routes::get_history,
+                stats::get_stats,
+                authors::get_author,
+                authors::validate_author,
+                events::create_event,
+                events::get_event,
+                events::register_player,
+                events::lock_event,
+                events::check_event,
+                jobs::create_job,
+                jobs::get_job,
+                jobs::job_ws,
+                ws::validate_ws,
+                sse::validate_sse,
+                admin::cache_stats,
+                admin::cache_entries,
+                admin::purge_cache,
+                admin::purge_cache_source
+            ],
+        )
+}
@@ -0,0 +1,179 @@
+use futures::FutureExt;
+use futures::future::{BoxFuture, Shared};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+type Call<V> = Shared<BoxFuture<'static, Result<V, String>>>;
+
+pub struct SingleFlight<V> {
+    calls: Arc<Mutex<HashMap<String, Call<V>>>>,
+}
+
+impl<V> Clone for SingleFlight<V> {
+    fn clone(&self) -> Self {
+        Self {
+            calls: self.calls.clone(),
+        }
+    }
+}
+
+impl<V> Default for SingleFlight<V> {
+    fn default() -> Self {
+        Self {
+            calls: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl<V: Clone + Send + Sync + 'static> SingleFlight<V> {
+    pub async fn run<F>(&self, key: String, fetch: F) -> Result<V, String>
+    where
+        F: Future<Output = Result<V, String>> + Send + 'static,
+    {
+        let _guard = AbandonGuard {
+            calls: self.calls.clone(),
+            key: key.clone(),
+        };
+        let call = {
+            let mut calls = self.calls.lock().unwrap();
+            match calls.get(&key) {
+                Some(call) => call.clone(),
+                None => {
+                    let calls_handle = self.calls.clone();
+                    let call_key = key.clone();
+                    let call = async move {
+                        let result = fetch.await;
+                        calls_handle.lock().unwrap().remove(&call_key);
+                        result
+                    }
+                    .boxed()
+                    .shared();
+                    calls.insert(key, call.clone());
+                    call
+                }
+            }
+        };
+
+        call.await
+    }
+
+    pub async fn run_many<F, Fut>(
+        &self,
+        keys: Vec<String>,
+        fetch: F,
+    ) -> HashMap<String, Result<V, String>>
+    where
+        F: FnOnce(Vec<String>) -> Fut,
+        Fut: Future<Output = HashMap<String, Result<V, String>>> + Send + 'static,
+    {
+        let _guards: Vec<AbandonGuard<V>> = keys
+            .iter()
+            .map(|key| AbandonGuard {
+                calls: self.calls.clone(),
+                key: key.clone(),
+            })
+            .collect();
+        let calls = {
+            let mut calls = self.calls.lock().unwrap();
+            let (joined, missing): (Vec<String>, Vec<String>) =
+                keys.into_iter().partition(|key| calls.contains_key(key));
+            let mut pending: Vec<(String, Call<V>)> = joined
+                .into_iter()
+                .map(|key| {
+                    let call = calls[&key].clone();
+                    (key, call)
+                })
+                .collect();
+
+            if !missing.is_empty() {
+                let batch = fetch(missing.clone()).boxed().shared();
+                for key in missing {
+                    let batch = batch.clone();
+                    let calls_handle = self.calls.clone();
+                    let call_key = key.clone();
+                    let call = async move {
+                        let result = batch
+                            .await
+                            .remove(&call_key)
+                            .unwrap_or_else(|| Err(format!("No result for {}", call_key)));
+                        calls_handle.lock().unwrap().remove(&call_key);
+                        result
+                    }
+                    .boxed()
+                    .shared();
+                    calls.insert(key.clone(), call.clone());
+                    pending.push((key, call));
+                }
+            }
+            pending
+        };
+
+        futures::future::join_all(
+            calls
+                .into_iter()
+                .map(|(key, call)| async move { (key, call.await) }),
+        )
+        .await
+        .into_iter()
+        .collect()
+    }
+}
+
+struct AbandonGuard<V> {
+    calls: Arc<Mutex<HashMap<String, Call<V>>>>,
+    key: String,
+}
+
+impl<V> Drop for AbandonGuard<V> {
+    fn drop(&mut self) {
+        let Ok(mut calls) = self.calls.lock() else {
+            return;
+        };
+        if calls
+            .get(&self.key)
+            .is_some_and(|call| call.strong_count() == Some(1))
+        {
+            calls.remove(&self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn coalesces_overlapping_batches_per_key() {
+        let flight: SingleFlight<usize> = SingleFlight::default();
+        let fetched = Arc::new(Mutex::new(Vec::new()));
+
+        let fetch = |fetched: Arc<Mutex<Vec<String>>>| {
+            move |keys: Vec<String>| async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                fetched.lock().unwrap().extend(keys.clone());
+                keys.into_iter()
+                    .map(|key| {
+                        let len = key.len();
+                        (key, Ok(len))
+                    })
+                    .collect()
+            }
+        };
+        let keys = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+
+        let (first, second) = tokio::join!(
+            flight.run_many(keys(&["Sol Ring", "Island"]), fetch(fetched.clone())),
+            flight.run_many(keys(&["Island", "Forest"]), fetch(fetched.clone())),
+        );
+
+        assert_eq!(first["Island"], Ok(6));
+        assert_eq!(second["Island"], Ok(6));
+        assert_eq!(second["Forest"], Ok(6));
+        let mut fetched = fetched.lock().unwrap().clone();
+        fetched.sort();
+        assert_eq!(fetched, ["Forest", "Island", "Sol Ring"]);
+        assert!(flight.calls.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,89 @@
+use crate::config::AppConfig;
+use crate::errors::AppError;
+use crate::inflight::{InFlightValidations, Resume};
+use crate::progress::ProgressMessage;
+use crate::request_id::RequestId;
+use crate::ruleset::Ruleset;
+use rocket::State;
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::response::stream::{Event, EventStream};
+use tokio::sync::broadcast;
+
+pub struct LastEventId(pub Option<usize>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for LastEventId {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let last_event_id = request
+            .headers()
+            .get_one("Last-Event-ID")
+            .and_then(|value| value.trim().parse().ok());
+        Outcome::Success(LastEventId(last_event_id))
+    }
+}
+
+fn event(message: &ProgressMessage) -> Event {
+    let event = Event::json(message);
+    match message.seq {
+        Some(seq) => event.id(seq.to_string()),
+        None => event,
+    }
+}
+
+#[get("/sse/validate/<id>?<ruleset>")]
+pub fn validate_sse(
+    id: String,
+    ruleset: Option<String>,
+    inflight: &State<InFlightValidations>,
+    config: &State<AppConfig>,
+    last_event_id: LastEventId,
+    request_id: &RequestId,
+) -> Result<EventStream![], AppError> {
+    let ruleset = match ruleset {
+        Some(name) => Ruleset::from_name(&name)
+            .ok_or_else(|| AppError::InvalidRequest(format!("Unknown ruleset {}", name)))?,
+        None => config.default_ruleset,
+    };
+
+    let subscription = inflight.join(
+        id,
+        ruleset,
+        request_id.0.clone(),
+        if last_event_id.0.is_some() {
+            Resume::Requested
+        } else {
+            Resume::Allowed
+        },
+    );
+    let resume_after = last_event_id.0.filter(|_| subscription.joined);
+
+    Ok(EventStream! {
+        let _watcher = subscription.watcher;
+        let mut rx = subscription.receiver;
+
+        for message in subscription.replay {
+            if resume_after.is_some_and(|last| message.seq.is_some_and(|seq| seq <= last)) {
+                continue;
+            }
+            yield event(&message);
+            if message.is_terminal() {
+                return;
+            }
+        }
+
+        loop {
+            match rx.recv().await {
+                Ok(message) => {
+                    yield event(&message);
+                    if message.is_terminal() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    })
+}
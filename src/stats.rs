@@ -0,0 +1,184 @@
+use crate::errors::AppError;
+use crate::persistence::{HistoryStore, StatsPeriod};
+use crate::validation_results::FindingCategory;
+use chrono::{DateTime, Utc};
+use rocket::{State, serde::json::Json};
+use serde::Serialize;
+
+const DEFAULT_STATS_LIMIT: u64 = 10;
+const MAX_STATS_LIMIT: u64 = 100;
+
+#[derive(Serialize, Debug)]
+pub struct PassRatePoint {
+    pub period: String,
+    pub total: u64,
+    pub passed: u64,
+    pub inconclusive: u64,
+    pub pass_rate: f64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct FlaggedCard {
+    pub card: String,
+    pub decks: u64,
+    pub failed_decks: u64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CategoryStats {
+    pub category: FindingCategory,
+    pub cards: Vec<FlaggedCard>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ComboStats {
+    pub cards: Vec<String>,
+    pub decks: u64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TutorAverages {
+    pub non_land_tutors: f64,
+    pub commander_tutors: f64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AuthorStats {
+    pub author: String,
+    pub reports: u64,
+    pub passed: u64,
+    pub inconclusive: u64,
+    pub pass_rate: f64,
+    pub decks: u64,
+    pub last_validated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct HistoryStats {
+    pub total_reports: u64,
+    pub passed_reports: u64,
+    pub inconclusive_reports: u64,
+    pub undated_reports: u64,
+    pub pass_rate: f64,
+    pub pass_rate_over_time: Vec<PassRatePoint>,
+    pub top_flagged_cards: Vec<CategoryStats>,
+    pub common_combos: Vec<ComboStats>,
+    pub average_tutors: TutorAverages,
+    pub authors: Vec<AuthorStats>,
+}
+
+fn pass_rate(passed: i64, total: i64, inconclusive: i64) -> f64 {
+    rate(passed as u64, (total - inconclusive) as u64)
+}
+
+fn rate(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+#[get("/stats?<period>&<limit>")]
+pub async fn get_stats(
+    period: Option<String>,
+    limit: Option<u64>,
+    store: &State<HistoryStore>,
+) -> Result<Json<HistoryStats>, AppError> {
+    let period = match period {
+        Some(name) => StatsPeriod::from_name(&name)
+            .ok_or_else(|| AppError::InvalidRequest(format!("Unknown period {}", name)))?,
+        None => StatsPeriod::default(),
+    };
+    let limit = limit
+        .unwrap_or(DEFAULT_STATS_LIMIT)
+        .clamp(1, MAX_STATS_LIMIT);
+
+    let totals = store.totals().await?;
+    let total_reports = totals.total as u64;
+
+    let pass_rate_over_time = store
+        .pass_rate_over_time(period)
+        .await?
+        .into_iter()
+        .map(|row| PassRatePoint {
+            pass_rate: pass_rate(row.passed, row.total, row.inconclusive),
+            period: row.period,
+            total: row.total as u64,
+            passed: row.passed as u64,
+            inconclusive: row.inconclusive as u64,
+        })
+        .collect();
+
+    let mut top_flagged_cards = Vec::new();
+    for category in FindingCategory::ALL {
+        let cards = store
+            .top_flagged_cards(category, limit)
+            .await?
+            .into_iter()
+            .map(|row| FlaggedCard {
+                card: row.card,
+                decks: row.decks as u64,
+                failed_decks: row.failed_decks as u64,
+            })
+            .collect();
+        top_flagged_cards.push(CategoryStats { category, cards });
+    }
+
+    let common_combos = store
+        .common_combos(limit)
+        .await?
+        .into_iter()
+        .map(|row| ComboStats {
+            cards: vec![row.first_card, row.second_card],
+            decks: row.decks as u64,
+        })
+        .collect();
+
+    let tutor_counts = store
+        .finding_counts(&[
+            FindingCategory::NonLandTutor,
+            FindingCategory::CommanderTutor,
+        ])
+        .await?;
+    let average = |category: FindingCategory| {
+        let findings = tutor_counts
+            .iter()
+            .find(|row| row.category == category.name())
+            .map(|row| row.findings as u64)
+            .unwrap_or_default();
+        rate(findings, total_reports)
+    };
+    let average_tutors = TutorAverages {
+        non_land_tutors: average(FindingCategory::NonLandTutor),
+        commander_tutors: average(FindingCategory::CommanderTutor),
+    };
+
+    let authors = store
+        .author_stats(limit)
+        .await?
+        .into_iter()
+        .map(|row| AuthorStats {
+            pass_rate: pass_rate(row.passed, row.reports, row.inconclusive),
+            author: row.author,
+            reports: row.reports as u64,
+            passed: row.passed as u64,
+            inconclusive: row.inconclusive as u64,
+            decks: row.decks as u64,
+            last_validated_at: row.last_validated_at,
+        })
+        .collect();
+
+    Ok(Json(HistoryStats {
+        total_reports,
+        passed_reports: totals.passed as u64,
+        inconclusive_reports: totals.inconclusive as u64,
+        undated_reports: totals.undated as u64,
+        pass_rate: pass_rate(totals.passed, totals.total, totals.inconclusive),
+        pass_rate_over_time,
+        top_flagged_cards,
+        common_combos,
+        average_tutors,
+        authors,
+    }))
+}
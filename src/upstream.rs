@@ -0,0 +1,268 @@
+use crate::cache::{CacheSource, LookupCache};
+use crate::errors::AppError;
+use crate::http::HttpClient;
+use crate::models::{
+    CardList, CardListUnit, ComboListRequest, DeckCards, List, ScryfallCard, ScryfallCollection,
+    ScryfallCollectionRequest, ScryfallIdentifier, ScryfallQuery,
+};
+use crate::scryfall::{SearchQuery, face_names, front_face};
+use crate::single_flight::SingleFlight;
+use crate::validation_results::FailMode;
+use reqwest::StatusCode;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+const SCRYFALL_COLLECTION_BATCH_SIZE: usize = 75;
+
+#[derive(Debug, Clone)]
+pub struct UpstreamConfig {
+    pub moxfield_api: String,
+    pub scryfall_api: String,
+    pub spellbook_api: String,
+    pub moxfield_user_agent: String,
+    pub scryfall_user_agent: String,
+    pub fail_mode: FailMode,
+}
+
+#[derive(Clone)]
+pub struct Upstream {
+    pub http: HttpClient,
+    pub cache: LookupCache,
+    pub config: Arc<UpstreamConfig>,
+    scryfall_calls: SingleFlight<Option<ScryfallCard>>,
+    spellbook_calls: SingleFlight<ComboListRequest>,
+}
+
+impl Upstream {
+    pub fn new(http: HttpClient, cache: LookupCache, config: UpstreamConfig) -> Self {
+        Self {
+            http,
+            cache,
+            config: Arc::new(config),
+            scryfall_calls: SingleFlight::default(),
+            spellbook_calls: SingleFlight::default(),
+        }
+    }
+
+    pub async fn resolve_cards(&self, list: &List) -> DeckCards {
+        let mut names: Vec<String> = list
+            .boards
+            .mainboard
+            .cards
+            .values()
+            .chain(list.boards.commanders.cards.values())
+            .map(|card| card.card.name.clone())
+            .collect();
+        names.sort();
+        names.dedup();
+
+        let mut cards = HashMap::new();
+        let mut not_found = HashSet::new();
+        let mut failed = HashMap::new();
+        let mut missing = Vec::new();
+        for name in names {
+            match self
+                .cache
+                .get::<Option<ScryfallCard>>(CacheSource::Scryfall, &name)
+                .await
+            {
+                Some(Some(card)) => {
+                    cards.insert(name, card);
+                }
+                Some(None) => {
+                    not_found.insert(name);
+                }
+                None => missing.push(name),
+            }
+        }
+
+        for batch in missing.chunks(SCRYFALL_COLLECTION_BATCH_SIZE) {
+            let upstream = self.clone();
+            let fetched = self
+                .scryfall_calls
+                .run_many(batch.to_vec(), |names| async move {
+                    match upstream.fetch_collection(names.clone()).await {
+                        Ok(fetched) => fetched,
+                        Err(e) => names
+                            .into_iter()
+                            .map(|name| (name, Err(e.clone())))
+                            .collect(),
+                    }
+                })
+                .await;
+
+            for (name, card) in fetched {
+                match card {
+                    Ok(Some(card)) => {
+                        cards.insert(name, card);
+                    }
+                    Ok(None) => {
+                        not_found.insert(name);
+                    }
+                    Err(e) => {
+                        failed.insert(name, e);
+                    }
+                }
+            }
+        }
+
+        DeckCards::new(cards, not_found, failed)
+    }
+
+    async fn fetch_collection(
+        &self,
+        names: Vec<String>,
+    ) -> Result<HashMap<String, Result<Option<ScryfallCard>, String>>, String> {
+        let body = ScryfallCollectionRequest {
+            identifiers: names
+                .iter()
+                .map(|name| ScryfallIdentifier {
+                    name: front_face(name).to_string(),
+                })
+                .collect(),
+        };
+        let request = self
+            .http
+            .post(format!("{}/cards/collection", self.config.scryfall_api))
+            .header("User-Agent", &self.config.scryfall_user_agent)
+            .header("Accept", "application/json")
+            .json(&body);
+        let response = self
+            .http
+            .send(request)
+            .await
+            .map_err(|e| e.to_string())?
+            .error_for_status()
+            .map_err(|e| e.to_string())?;
+        let collection = response
+            .json::<ScryfallCollection>()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let mut by_name: HashMap<String, ScryfallCard> = HashMap::new();
+        for card in collection.data {
+            let face_names: Vec<String> = face_names(&card.name)
+                .map(str::to_string)
+                .chain(
+                    card.card_faces
+                        .iter()
+                        .flatten()
+                        .map(|face| face.name.clone()),
+                )
+                .collect();
+            for face in face_names {
+                by_name.insert(face.to_lowercase(), card.clone());
+            }
+            by_name.insert(card.name.to_lowercase(), card);
+        }
+
+        let lookups = names.into_iter().map(|name| {
+            let matched = by_name
+                .get(&name.to_lowercase())
+                .or_else(|| by_name.get(&front_face(&name).to_lowercase()))
+                .cloned();
+            async move {
+                let card = match matched {
+                    Some(card) => Ok(Some(card)),
+                    None => self.search_exact(&name).await,
+                };
+                if let Ok(card) = &card {
+                    self.cache
+                        .insert(CacheSource::Scryfall, name.clone(), card)
+                        .await;
+                }
+                (name, card)
+            }
+        });
+        Ok(futures::future::join_all(lookups)
+            .await
+            .into_iter()
+            .collect())
+    }
+
+    async fn search_exact(&self, name: &str) -> Result<Option<ScryfallCard>, String> {
+        let url = SearchQuery::new()
+            .exact_name(name)
+            .include_extras()
+            .to_url(&self.config.scryfall_api);
+        let request = self
+            .http
+            .get(url)
+            .header("User-Agent", &self.config.scryfall_user_agent)
+            .header("Accept", "application/json");
+        let response = self.http.send(request).await.map_err(|e| e.to_string())?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let query_result = response
+            .error_for_status()
+            .map_err(|e| e.to_string())?
+            .json::<ScryfallQuery>()
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(query_result.data.into_iter().next())
+    }
+
+    pub async fn get_combos(&self, list: &List) -> Result<ComboListRequest, AppError> {
+        let mut card_names: Vec<String> = list
+            .boards
+            .mainboard
+            .cards
+            .values()
+            .chain(list.boards.commanders.cards.values())
+            .map(|card| card.card.name.clone())
+            .collect();
+        card_names.sort();
+
+        let cache_key = card_names.join("|");
+        if let Some(result) = self.cache.get(CacheSource::Spellbook, &cache_key).await {
+            return Ok(result);
+        }
+
+        let upstream = self.clone();
+        self.spellbook_calls
+            .run(cache_key.clone(), async move {
+                upstream.fetch_combos(cache_key, card_names).await
+            })
+            .await
+            .map_err(AppError::SpellbookApiError)
+    }
+
+    async fn fetch_combos(
+        &self,
+        cache_key: String,
+        card_names: Vec<String>,
+    ) -> Result<ComboListRequest, String> {
+        let cards: Vec<CardListUnit> = card_names
+            .into_iter()
+            .map(|card| CardListUnit { card, quantity: 1 })
+            .collect();
+
+        let card_list = CardList { main: cards };
+
+        let request = self
+            .http
+            .post(format!("{}/find-my-combos", self.config.spellbook_api))
+            .header("Content-Type", "application/json")
+            .json(&card_list);
+        let response = self.http.send(request).await.map_err(|e| e.to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!(
+                "Commander Spellbook returned {}",
+                response.status()
+            ));
+        }
+        let result = response
+            .json::<ComboListRequest>()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        self.cache
+            .insert(CacheSource::Spellbook, cache_key, &result)
+            .await;
+        Ok(result)
+    }
+}
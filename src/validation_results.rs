@@ -1,3 +1,24 @@
+use crate::ruleset::Ruleset;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FailMode {
+    Open,
+    #[default]
+    Closed,
+}
+
+impl FailMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "open" => Some(FailMode::Open),
+            "closed" => Some(FailMode::Closed),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct ValidationResults {
     pub mass_land_denial_cards: Vec<(String, String)>,
@@ -7,6 +28,8 @@ pub struct ValidationResults {
     pub gamechangers: Vec<String>,
     pub infinite_turns_combos: Vec<Vec<String>>,
     pub combos: Vec<(Vec<String>, String)>,
+    pub incomplete_lookups: Vec<String>,
+    pub unknown_cards: Vec<String>,
 }
 
 impl ValidationResults {
@@ -19,6 +42,8 @@ impl ValidationResults {
         self.gamechangers.extend(other.gamechangers);
         self.infinite_turns_combos
             .extend(other.infinite_turns_combos);
+        self.incomplete_lookups.extend(other.incomplete_lookups);
+        self.unknown_cards.extend(other.unknown_cards);
 
         if self.combos.is_empty() && !other.combos.is_empty() {
             self.combos = other.combos;
@@ -27,12 +52,112 @@ impl ValidationResults {
         self
     }
 
-    pub fn is_valid(&self) -> bool {
+    pub fn is_valid(&self, ruleset: &Ruleset) -> bool {
         self.mass_land_denial_cards.is_empty()
-            && self.non_land_tutors.len() <= 3
-            && self.commander_tutors.is_empty()
-            && self.two_card_combos.is_empty()
-            && self.gamechangers.is_empty()
+            && ruleset
+                .max_non_land_tutors()
+                .is_none_or(|max| self.non_land_tutors.len() <= max)
+            && (ruleset.allows_commander_tutors() || self.commander_tutors.is_empty())
+            && (ruleset.allows_two_card_combos() || self.two_card_combos.is_empty())
+            && self.gamechangers.len() <= ruleset.max_gamechangers()
             && self.infinite_turns_combos.is_empty()
     }
 }
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingCategory {
+    MassLandDenial,
+    NonLandTutor,
+    CommanderTutor,
+    TwoCardCombo,
+    Gamechanger,
+    InfiniteTurns,
+}
+
+impl FindingCategory {
+    pub const ALL: [FindingCategory; 6] = [
+        FindingCategory::MassLandDenial,
+        FindingCategory::NonLandTutor,
+        FindingCategory::CommanderTutor,
+        FindingCategory::TwoCardCombo,
+        FindingCategory::Gamechanger,
+        FindingCategory::InfiniteTurns,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FindingCategory::MassLandDenial => "mass_land_denial",
+            FindingCategory::NonLandTutor => "non_land_tutor",
+            FindingCategory::CommanderTutor => "commander_tutor",
+            FindingCategory::TwoCardCombo => "two_card_combo",
+            FindingCategory::Gamechanger => "gamechanger",
+            FindingCategory::InfiniteTurns => "infinite_turns",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.name().eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Finding {
+    pub category: FindingCategory,
+    pub cards: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl ValidationResults {
+    pub fn findings(&self) -> Vec<Finding> {
+        let finding = |category, cards: Vec<String>, detail: Option<&String>| Finding {
+            category,
+            cards,
+            detail: detail.cloned(),
+        };
+
+        let mut findings = Vec::new();
+        for (card, oracle_text) in &self.mass_land_denial_cards {
+            findings.push(finding(
+                FindingCategory::MassLandDenial,
+                vec![card.clone()],
+                Some(oracle_text),
+            ));
+        }
+        for (card, oracle_text) in &self.non_land_tutors {
+            findings.push(finding(
+                FindingCategory::NonLandTutor,
+                vec![card.clone()],
+                Some(oracle_text),
+            ));
+        }
+        for card in &self.commander_tutors {
+            findings.push(finding(
+                FindingCategory::CommanderTutor,
+                vec![card.clone()],
+                None,
+            ));
+        }
+        for (cards, description) in &self.two_card_combos {
+            findings.push(finding(
+                FindingCategory::TwoCardCombo,
+                cards.clone(),
+                Some(description),
+            ));
+        }
+        for card in &self.gamechangers {
+            findings.push(finding(
+                FindingCategory::Gamechanger,
+                vec![card.clone()],
+                None,
+            ));
+        }
+        for cards in &self.infinite_turns_combos {
+            findings.push(finding(FindingCategory::InfiniteTurns, cards.clone(), None));
+        }
+        findings
+    }
+}
@@ -1,113 +1,118 @@
+use crate::config::AppConfig;
 use crate::errors::AppError;
-use crate::models::List;
-use crate::persistence::HistoryStore;
-use crate::progress::ProgressTracker;
-use crate::validators::{
-    CommanderTutorValidator, GamechangerValidator, InfiniteTurnsValidator, MassLandDenialValidator,
-    NonLandTutorValidator, TwoCardComboValidator,
-};
+use crate::inflight::{InFlightValidations, Resume, Watcher};
+use crate::progress::{ProgressEvent, ProgressMessage};
+use crate::request_id::RequestId;
+use crate::ruleset::Ruleset;
 use rocket::State;
 use rocket::futures::{SinkExt, StreamExt};
 use rocket_ws::{Channel, Message, WebSocket};
-use std::sync::{Arc, Mutex};
+use serde::Deserialize;
+use tokio::sync::broadcast;
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Start {
+        #[serde(default)]
+        ruleset: Option<Ruleset>,
+    },
+    Cancel,
+    Ping,
+}
+
+fn frame(event: ProgressEvent) -> Message {
+    Message::Text(ProgressMessage::new(None, event).to_json())
+}
+
+fn error_frame(error: AppError, request_id: &str) -> Message {
+    frame(ProgressEvent::Error(
+        error.to_body(Some(request_id.to_string())),
+    ))
+}
 
 #[get("/ws/validate/<id>")]
 pub fn validate_ws(
     id: String,
     ws: WebSocket,
-    client: &State<reqwest::Client>,
-    store: &State<HistoryStore>,
+    inflight: &State<InFlightValidations>,
+    config: &State<AppConfig>,
+    request_id: &RequestId,
 ) -> Channel<'static> {
-    let client = client.inner().clone();
-    let store = store.inner().clone();
-    let id_clone = id.clone();
+    let request_id = request_id.0.clone();
+    let inflight = inflight.inner().clone();
+    let default_ruleset = config.default_ruleset;
 
     ws.channel(move |stream| {
         Box::pin(async move {
-            let tracker = Arc::new(Mutex::new(ProgressTracker::new(6)));
-
-            let mut rx = {
-                let tracker_guard = tracker.lock().unwrap();
-                tracker_guard.subscribe()
-            };
-
-            let (mut sender, _receiver) = stream.split();
-
-            let tracker_clone = tracker.clone();
-
-            let mut validation_task = Box::pin(tokio::spawn(async move {
-                let user_agent = std::env::var("MOXFIELD_USER_AGENT")
-                    .unwrap();
-
-                let response = client
-                    .get("https://api2.moxfield.com/v3/decks/all/".to_string() + &id_clone)
-                    .header("User-Agent", &user_agent)
-                    .header("Accept", "application/json")
-                    .send()
-                    .await
-                    .map_err(|e| AppError::ScryfallApiError(e.to_string()))?;
-
-                let list = response
-                    .json::<List>()
-                    .await
-                    .map_err(|e| AppError::ScryfallApiError(e.to_string()))?;
-
-                let report = list
-                    .validate_with_progress(
-                        &client,
-                        vec![
-                            Box::new(MassLandDenialValidator),
-                            Box::new(NonLandTutorValidator),
-                            Box::new(CommanderTutorValidator),
-                            Box::new(GamechangerValidator),
-                            Box::new(InfiniteTurnsValidator),
-                            Box::new(TwoCardComboValidator),
-                        ],
-                        Some(tracker_clone),
-                    )
-                    .await?;
-
-                store.save(report.clone()).await?;
-                Ok::<_, AppError>(report)
-            }));
+            let (mut sender, mut receiver) = stream.split();
+            let mut watcher: Option<Watcher> = None;
+            let mut rx: Option<broadcast::Receiver<ProgressMessage>> = None;
 
             loop {
                 tokio::select! {
-                    res = &mut validation_task => {
-                        match res {
-                             Ok(Ok(report)) => {
-                                 if let Ok(json) = serde_json::to_string(&report) {
-                                      let _ = sender.send(Message::Text(json)).await;
-                                 }
-                             },
-                             Ok(Err(e)) => {
-                                 let error_msg = serde_json::json!({
-                                     "type": "error",
-                                     "message": format!("{:?}", e)
-                                 });
-                                 let _ = sender.send(Message::Text(error_msg.to_string())).await;
-                             },
-                             Err(e) => {
-                                 let error_msg = serde_json::json!({
-                                     "type": "error",
-                                     "message": format!("Task failed: {:?}", e)
-                                 });
-                                 let _ = sender.send(Message::Text(error_msg.to_string())).await;
-                             }
+                    msg = async { rx.as_mut().unwrap().recv().await }, if rx.is_some() => {
+                        match msg {
+                            Ok(progress_msg) => {
+                                let _ = sender.send(Message::Text(progress_msg.to_json())).await;
+                                if progress_msg.is_terminal() {
+                                    break;
+                                }
+                            }
+                            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                            Err(broadcast::error::RecvError::Closed) => break,
                         }
-                        break;
                     },
-                    msg = rx.recv() => {
-                        if let Ok(progress_msg) = msg {
-                            if let Ok(json) = serde_json::to_string(&progress_msg) {
-                                 let _ = sender.send(Message::Text(json)).await;
+                    incoming = receiver.next() => {
+                        let text = match incoming {
+                            Some(Ok(Message::Text(text))) => text,
+                            Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                            Some(Ok(_)) => continue,
+                        };
+
+                        match serde_json::from_str::<ClientMessage>(&text) {
+                            Ok(ClientMessage::Start { ruleset }) => {
+                                if rx.is_some() {
+                                    let error = AppError::InvalidRequest(
+                                        "Validation already started".to_string(),
+                                    );
+                                    let _ = sender.send(error_frame(error, &request_id)).await;
+                                    continue;
+                                }
+
+                                let ruleset = ruleset.unwrap_or(default_ruleset);
+                                let subscription = inflight.join(id.clone(), ruleset, request_id.clone(), Resume::Never);
+                                let finished = subscription.replay.last().is_some_and(|msg| msg.is_terminal());
+                                watcher = Some(subscription.watcher);
+                                rx = Some(subscription.receiver);
+                                for progress_msg in subscription.replay {
+                                    let _ = sender.send(Message::Text(progress_msg.to_json())).await;
+                                }
+                                if finished {
+                                    break;
+                                }
+                            }
+                            Ok(ClientMessage::Cancel) => {
+                                if let Some(watcher) = watcher.take() {
+                                    watcher.cancel();
+                                }
+                                let _ = sender.send(frame(ProgressEvent::Cancelled)).await;
+                                break;
+                            }
+                            Ok(ClientMessage::Ping) => {
+                                let _ = sender.send(frame(ProgressEvent::Pong)).await;
+                            }
+                            Err(e) => {
+                                let error = AppError::InvalidRequest(format!("Invalid message: {}", e));
+                                let _ = sender.send(error_frame(error, &request_id)).await;
                             }
-                        } else {
                         }
                     }
                 }
             }
 
+            drop(watcher);
+
             Ok(())
         })
     })
@@ -0,0 +1,155 @@
+#![cfg(feature = "server")]
+
+use futures::future::join_all;
+use moxfield_list_verifyer::errors::AppError;
+use moxfield_list_verifyer::migrator::Migrator;
+use moxfield_list_verifyer::models::{CardListUnit, Report};
+use moxfield_list_verifyer::persistence::EventStore;
+use sea_orm::Database;
+use sea_orm_migration::MigratorTrait;
+
+async fn store() -> EventStore {
+    let conn = Database::connect("sqlite::memory:").await.unwrap();
+    Migrator::up(&conn, None).await.unwrap();
+    EventStore::new(conn)
+}
+
+fn deck_list() -> Vec<CardListUnit> {
+    vec![CardListUnit {
+        card: "Sol Ring".to_string(),
+        quantity: 1,
+    }]
+}
+
+#[tokio::test]
+async fn locking_closes_registration() {
+    let store = store().await;
+    let event = store
+        .create_event("League".to_string(), "house".to_string())
+        .await
+        .unwrap();
+    store
+        .add_player(event.id, "Alice".to_string(), "abc".to_string())
+        .await
+        .unwrap();
+
+    let (locked, players) = store.lock_event(event.id).await.unwrap();
+    assert!(locked.locked_at.is_some());
+    assert_eq!(players.len(), 1);
+
+    let late = store
+        .add_player(event.id, "Bob".to_string(), "def".to_string())
+        .await;
+    assert!(matches!(late, Err(AppError::EventLocked(id)) if id == event.id));
+}
+
+#[tokio::test]
+async fn registrations_racing_the_lock_are_either_snapshotted_or_rejected() {
+    let store = store().await;
+    let event = store
+        .create_event("League".to_string(), "house".to_string())
+        .await
+        .unwrap();
+
+    let registrations = join_all((0..20).map(|i| {
+        let store = store.clone();
+        async move {
+            store
+                .add_player(event.id, format!("Player {}", i), format!("deck{}", i))
+                .await
+        }
+    }));
+    let (registered, locked) = tokio::join!(registrations, store.lock_event(event.id));
+
+    let (_, mut snapshotted) = locked.unwrap();
+    let mut accepted = Vec::new();
+    for result in registered {
+        match result {
+            Ok(player) => accepted.push(player.id),
+            Err(AppError::EventLocked(_)) => {}
+            Err(e) => panic!("unexpected registration error: {}", e),
+        }
+    }
+    snapshotted.sort_by_key(|player| player.id);
+    let snapshotted: Vec<_> = snapshotted.iter().map(|player| player.id).collect();
+    accepted.sort();
+    assert_eq!(snapshotted, accepted);
+}
+
+#[tokio::test]
+async fn failed_snapshots_can_be_locked_again() {
+    let store = store().await;
+    let event = store
+        .create_event("League".to_string(), "house".to_string())
+        .await
+        .unwrap();
+    for (name, deck_id) in [("Alice", "abc"), ("Bob", "def")] {
+        store
+            .add_player(event.id, name.to_string(), deck_id.to_string())
+            .await
+            .unwrap();
+    }
+
+    let (_, players) = store.lock_event(event.id).await.unwrap();
+    let (alice, bob) = (players[0].clone(), players[1].clone());
+    store
+        .lock_players(vec![
+            (alice, Ok(deck_list())),
+            (bob, Err("Moxfield is unavailable".to_string())),
+        ])
+        .await
+        .unwrap();
+
+    let (_, missing) = store.lock_event(event.id).await.unwrap();
+    assert_eq!(missing.len(), 1);
+    assert_eq!(missing[0].player_name, "Bob");
+    assert_eq!(missing[0].error.as_deref(), Some("Moxfield is unavailable"));
+
+    store
+        .lock_players(vec![(missing[0].clone(), Ok(deck_list()))])
+        .await
+        .unwrap();
+    let players = store.get_players(event.id).await.unwrap();
+    assert!(
+        players
+            .iter()
+            .all(|player| player.locked_deck_list.is_some())
+    );
+    assert!(players.iter().all(|player| player.error.is_none()));
+
+    let relocked = store.lock_event(event.id).await;
+    assert!(matches!(relocked, Err(AppError::EventLocked(_))));
+}
+
+#[tokio::test]
+async fn checks_record_every_player_outcome() {
+    let store = store().await;
+    let event = store
+        .create_event("League".to_string(), "house".to_string())
+        .await
+        .unwrap();
+    for (name, deck_id) in [("Alice", "abc"), ("Bob", "def")] {
+        store
+            .add_player(event.id, name.to_string(), deck_id.to_string())
+            .await
+            .unwrap();
+    }
+
+    let players = store.get_players(event.id).await.unwrap();
+    let mut report = Report::new("Deck".to_string(), "Alice".to_string(), deck_list());
+    report.is_valid = true;
+    store
+        .record_checks(vec![
+            (players[0].clone(), Ok((report, true))),
+            (players[1].clone(), Err("Deck def not found".to_string())),
+        ])
+        .await
+        .unwrap();
+
+    let players = store.get_players(event.id).await.unwrap();
+    assert_eq!(players[0].is_valid, Some(true));
+    assert!(players[0].changed_after_lock);
+    assert_eq!(players[1].is_valid, None);
+    assert_eq!(players[1].error.as_deref(), Some("Deck def not found"));
+    assert!(players.iter().all(|player| player.checked_at.is_some()));
+}
@@ -0,0 +1,203 @@
+#![cfg(feature = "server")]
+
+use chrono::{TimeZone, Utc};
+use moxfield_list_verifyer::migrator::Migrator;
+use moxfield_list_verifyer::models::{CardListUnit, Report};
+use moxfield_list_verifyer::persistence::{HistoryFilter, HistoryStore, StatsPeriod};
+use moxfield_list_verifyer::ruleset::Ruleset;
+use sea_orm::{ConnectOptions, ConnectionTrait, Database, DatabaseConnection, Statement};
+use sea_orm_migration::MigratorTrait;
+
+fn report(name: &str, day: u32, is_valid: bool) -> Report {
+    let mut report = Report::new(
+        name.to_string(),
+        "Tester".to_string(),
+        vec![
+            CardListUnit {
+                card: "Sol Ring".to_string(),
+                quantity: 1,
+            },
+            CardListUnit {
+                card: "Island".to_string(),
+                quantity: 30,
+            },
+        ],
+    );
+    report.is_valid = is_valid;
+    report.non_land_tutors = vec![(
+        "Demonic Tutor".to_string(),
+        "Search your library for a card, put that card into your hand, then shuffle.".to_string(),
+    )];
+    report.two_card_combos = vec![(
+        vec![
+            "Thassa's Oracle".to_string(),
+            "Demonic Consultation".to_string(),
+        ],
+        "Win the game".to_string(),
+    )];
+    report.unknown_cards = vec!["Sol Rnig".to_string()];
+    report.ruleset = Some(Ruleset::House);
+    report.created_at = Some(Utc.with_ymd_and_hms(2026, 10, day, 12, 30, 15).unwrap());
+    report.content_hash = Some(format!("hash-{}", name));
+    report.deck_id = Some(format!("deck-{}", name));
+    report
+}
+
+async fn migrate_and_round_trip(conn: &DatabaseConnection) {
+    Migrator::up(conn, None).await.unwrap();
+    let store = HistoryStore::new(conn.clone());
+
+    let saved = report("first", 5, false);
+    store.save(saved.clone()).await.unwrap();
+    store.save(report("second", 20, true)).await.unwrap();
+
+    let found = store
+        .find(&HistoryFilter {
+            author: Some("tester".to_string()),
+            flagged_card: Some("demonic tutor".to_string()),
+            is_valid: Some(false),
+            ..HistoryFilter::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(found.len(), 1);
+    let found = &found[0];
+    assert_eq!(found.name, saved.name);
+    assert_eq!(found.deck_list, saved.deck_list);
+    assert_eq!(found.non_land_tutors, saved.non_land_tutors);
+    assert_eq!(found.two_card_combos, saved.two_card_combos);
+    assert_eq!(found.unknown_cards, saved.unknown_cards);
+    assert_eq!(found.ruleset, saved.ruleset);
+    assert_eq!(found.created_at, saved.created_at);
+    assert_eq!(found.deck_id, saved.deck_id);
+
+    // Reports saved before created_at and deck_id existed.
+    store.save(report("legacy", 1, true)).await.unwrap();
+    conn.execute_unprepared(
+        "UPDATE report SET created_at = NULL, deck_id = NULL WHERE name = 'legacy'",
+    )
+    .await
+    .unwrap();
+
+    let totals = store.totals().await.unwrap();
+    assert_eq!((totals.total, totals.passed, totals.undated), (3, 2, 1));
+
+    let authors = store.author_stats(10).await.unwrap();
+    assert_eq!((authors[0].reports, authors[0].decks), (3, 2));
+
+    store.save(report("second", 21, false)).await.unwrap();
+    let decks = store.author_decks("TESTER").await.unwrap();
+    let decks: Vec<_> = decks
+        .iter()
+        .map(|row| (row.deck_id.as_deref(), row.checks, row.passed, row.is_valid))
+        .collect();
+    assert_eq!(
+        decks,
+        [
+            (Some("deck-second"), 2, 1, false),
+            (None, 1, 1, true),
+            (Some("deck-first"), 1, 0, false),
+        ]
+    );
+
+    let days = store.pass_rate_over_time(StatsPeriod::Day).await.unwrap();
+    let days: Vec<_> = days
+        .iter()
+        .map(|row| (row.period.as_str(), row.total, row.passed))
+        .collect();
+    assert_eq!(
+        days,
+        [
+            ("2026-10-05", 1, 0),
+            ("2026-10-20", 1, 1),
+            ("2026-10-21", 1, 0)
+        ]
+    );
+
+    let months = store.pass_rate_over_time(StatsPeriod::Month).await.unwrap();
+    assert_eq!(months.len(), 1);
+    assert_eq!((months[0].period.as_str(), months[0].total), ("2026-10", 3));
+
+    let since = Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap();
+    let unchanged = store
+        .find_unchanged("hash-first", "deck-first", since)
+        .await
+        .unwrap();
+    assert!(
+        unchanged.is_none(),
+        "reports with unknown cards are not reused"
+    );
+
+    let mut clean = report("clean", 6, true);
+    clean.unknown_cards.clear();
+    store.save(clean.clone()).await.unwrap();
+    let mut skipped = report("skipped", 6, true);
+    skipped.unknown_cards.clear();
+    skipped.incomplete_lookups = vec!["Scryfall: lookup failed for Sol Ring".to_string()];
+    store.save(skipped).await.unwrap();
+
+    let unchanged = store
+        .find_unchanged("hash-clean", "deck-clean", since)
+        .await
+        .unwrap();
+    assert_eq!(unchanged.map(|report| report.name), Some(clean.name));
+    let unchanged = store
+        .find_unchanged("hash-skipped", "deck-skipped", since)
+        .await
+        .unwrap();
+    assert!(
+        unchanged.is_none(),
+        "reports with skipped lookups are not reused"
+    );
+}
+
+#[tokio::test]
+async fn migrates_and_round_trips_reports_on_sqlite() {
+    let conn = Database::connect("sqlite::memory:").await.unwrap();
+    migrate_and_round_trip(&conn).await;
+}
+
+// Run with `DATABASE_URL=postgres://... cargo test -- --ignored`; uses a throwaway schema.
+#[tokio::test]
+#[ignore = "needs a Postgres DATABASE_URL"]
+async fn migrates_and_round_trips_reports_on_postgres() {
+    let url = std::env::var("DATABASE_URL")
+        .ok()
+        .filter(|url| url.starts_with("postgres://") || url.starts_with("postgresql://"))
+        .expect("DATABASE_URL must be a Postgres URL");
+
+    let schema = format!("history_store_{}", uuid::Uuid::new_v4().simple());
+    let admin = Database::connect(&url).await.unwrap();
+    admin
+        .execute_unprepared(&format!("CREATE SCHEMA {}", schema))
+        .await
+        .unwrap();
+
+    let mut options = ConnectOptions::new(&url);
+    options.set_schema_search_path(&schema);
+    let conn = Database::connect(options).await.unwrap();
+    migrate_and_round_trip(&conn).await;
+
+    let json_types = conn
+        .query_all(Statement::from_string(
+            conn.get_database_backend(),
+            format!(
+                "SELECT data_type FROM information_schema.columns \
+                 WHERE table_schema = '{}' AND table_name = 'report' \
+                 AND column_name IN ('deck_list', 'non_land_tutors', 'incomplete_lookups')",
+                schema
+            ),
+        ))
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| row.try_get::<String>("", "data_type").unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(json_types, ["jsonb"; 3]);
+
+    conn.close().await.unwrap();
+    admin
+        .execute_unprepared(&format!("DROP SCHEMA {} CASCADE", schema))
+        .await
+        .unwrap();
+}